@@ -17,6 +17,9 @@ pub struct Bridge {
     pub bridge_deposit:        u128,
     #[arg(short, long)]
     pub args:                  String,
+    /// The consensus time to run the bridge at, in ms since the unix epoch
+    #[arg(long)]
+    pub timestamp:             u64,
 }
 
 impl Bridge {
@@ -36,7 +39,7 @@ impl Bridge {
             self.args,
         ];
 
-        let response: Vec<String> = client.request("cli", rpc_params!(args)).await?;
+        let response: Vec<String> = client.request("cli", rpc_params!(args, self.timestamp)).await?;
         // This is assuming we are always putting valid json in our wasm output...
         // This formatting is also a bit awkward...
         serde_json::to_writer_pretty(std::io::stdout(), &response)?;
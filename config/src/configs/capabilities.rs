@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The manifest of what a WASM binary running in limited mode may do. A `*`
+/// entry allows everything of its kind, anything not declared is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// The promise actions the binary may queue, by name e.g. `http` or
+    /// `db_get`
+    #[serde(default)]
+    pub actions:       Vec<String>,
+    /// The domains `http` promises may request, their subdomains included
+    #[serde(default)]
+    pub http_domains:  Vec<String>,
+    /// The contracts chain promises may view or call, by chain e.g. `near`
+    #[serde(default)]
+    pub contracts:     BTreeMap<String, Vec<String>>,
+    /// The shared database namespaces the binary may use, its own namespace
+    /// is always allowed
+    #[serde(default)]
+    pub db_namespaces: Vec<String>,
+}
+
+impl Capabilities {
+    pub const ANY: &str = "*";
+
+    /// The capabilities of a binary without a manifest, it may call itself and
+    /// make HTTP requests to any domain.
+    pub fn without_manifest() -> Self {
+        Self {
+            actions: vec!["call_self".to_string(), "http".to_string()],
+            http_domains: vec![Self::ANY.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn allows(entries: &[String], value: &str) -> bool {
+        entries.iter().any(|entry| entry == Self::ANY || entry == value)
+    }
+
+    pub fn allows_action(&self, action: &str) -> bool {
+        Self::allows(&self.actions, action)
+    }
+
+    /// Whether the lowercased `domain` is declared or a subdomain of one that
+    /// is.
+    pub fn allows_http_domain(&self, domain: &str) -> bool {
+        self.http_domains.iter().any(|entry| {
+            let entry = entry.to_ascii_lowercase();
+            entry == Self::ANY
+                || entry == domain
+                || domain
+                    .strip_suffix(entry.as_str())
+                    .map_or(false, |subdomain| subdomain.ends_with('.'))
+        })
+    }
+
+    pub fn allows_contract(&self, chain: &str, contract_id: &str) -> bool {
+        self.contracts
+            .get(chain)
+            .map_or(false, |contracts| Self::allows(contracts, contract_id))
+    }
+
+    pub fn allows_db_namespace(&self, namespace: &str) -> bool {
+        Self::allows(&self.db_namespaces, namespace)
+    }
+}
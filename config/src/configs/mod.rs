@@ -1,3 +1,6 @@
+mod capabilities;
+pub use capabilities::*;
+
 mod logger;
 pub use logger::*;
 
@@ -1,7 +1,8 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
+use crate::Capabilities;
 #[cfg(feature = "cli")]
 use crate::{env_overwrite, merge_config_cli, Config, ConfigError, Result};
 
@@ -40,6 +41,40 @@ pub struct PartialNodeConfig {
     /// An option to override the node runtime worker threads config value.
     #[arg(long)]
     pub runtime_worker_threads:    Option<u8>,
+    /// An option to override the node runtime job timeout(ms) config value.
+    #[arg(long)]
+    pub job_timeout_ms:            Option<u64>,
+    /// An option to override the node runtime job gas limit config value.
+    #[arg(long)]
+    pub gas_limit:                 Option<u64>,
+    /// An option to override the node runtime promise timeout(ms) config value.
+    #[arg(long)]
+    pub promise_timeout_ms:        Option<u64>,
+    /// An option to override the node runtime max memory pages config value.
+    #[arg(long)]
+    pub max_memory_pages:          Option<u32>,
+    /// An option to override the node runtime max promise generations config
+    /// value.
+    #[arg(long)]
+    pub max_promise_generations:   Option<u32>,
+    /// The path where the compiled WASM modules are cached.
+    #[arg(long)]
+    pub wasm_cache_path:           Option<PathBuf>,
+    /// The path of the node database.
+    #[arg(long)]
+    pub db_path:                   Option<PathBuf>,
+    /// The database namespaces every WASM binary can use besides its own.
+    #[arg(long)]
+    pub shared_db_namespaces:      Option<Vec<String>>,
+    /// Option to keep the promise queue trace of every job.
+    #[arg(long)]
+    pub trace_jobs:                Option<bool>,
+    /// The WASM binaries the node can run by name.
+    #[arg(skip)]
+    pub wasm_binaries:             Option<BTreeMap<String, WasmBinarySource>>,
+    /// The capabilities of the binaries that run in limited mode by name.
+    #[arg(skip)]
+    pub wasm_capabilities:         Option<BTreeMap<String, Capabilities>>,
 }
 #[cfg(feature = "cli")]
 impl PartialNodeConfig {
@@ -101,6 +136,51 @@ impl PartialNodeConfig {
             Ok(NodeConfigInner::RUNTIME_WORKER_THREADS),
             |f| f as usize
         )?;
+        let job_timeout_ms = merge_config_cli!(self, cli_options, job_timeout_ms, Ok(NodeConfigInner::JOB_TIMEOUT_MS))?;
+        let gas_limit = merge_config_cli!(self, cli_options, gas_limit, Ok(NodeConfigInner::GAS_LIMIT))?;
+        let promise_timeout_ms = merge_config_cli!(
+            self,
+            cli_options,
+            promise_timeout_ms,
+            Ok(NodeConfigInner::PROMISE_TIMEOUT_MS)
+        )?;
+        let max_memory_pages = merge_config_cli!(
+            self,
+            cli_options,
+            max_memory_pages,
+            Ok(NodeConfigInner::MAX_MEMORY_PAGES)
+        )?;
+        let max_promise_generations = merge_config_cli!(
+            self,
+            cli_options,
+            max_promise_generations,
+            Ok(NodeConfigInner::MAX_PROMISE_GENERATIONS)
+        )?;
+        let wasm_cache_path = merge_config_cli!(
+            self,
+            cli_options,
+            wasm_cache_path,
+            std::env::current_dir()
+                .map(|dir| dir.join(NodeConfigInner::WASM_CACHE_DIR))
+                .map_err(|e| ConfigError::FailedToGetCurrentDir(e.to_string()))
+        )?;
+        let db_path = merge_config_cli!(
+            self,
+            cli_options,
+            db_path,
+            std::env::current_dir()
+                .map(|dir| dir.join(NodeConfigInner::DB_FILE))
+                .map_err(|e| ConfigError::FailedToGetCurrentDir(e.to_string()))
+        )?;
+        let shared_db_namespaces = merge_config_cli!(self, cli_options, shared_db_namespaces, Ok(Vec::new()))?;
+        let trace_jobs = merge_config_cli!(self, cli_options, trace_jobs, Ok(false))?;
+        let wasm_binaries = merge_config_cli!(
+            self,
+            cli_options,
+            wasm_binaries,
+            Ok(NodeConfigInner::default_wasm_binaries())
+        )?;
+        let wasm_capabilities = merge_config_cli!(self, cli_options, wasm_capabilities, Ok(BTreeMap::new()))?;
 
         Ok(Arc::new(NodeConfigInner {
             deposit,
@@ -113,6 +193,17 @@ impl PartialNodeConfig {
             public_key,
             job_manager_interval_ms,
             runtime_worker_threads,
+            job_timeout_ms,
+            gas_limit,
+            promise_timeout_ms,
+            max_memory_pages,
+            max_promise_generations,
+            wasm_cache_path,
+            db_path,
+            shared_db_namespaces,
+            trace_jobs,
+            wasm_binaries,
+            wasm_capabilities,
         }))
     }
 }
@@ -131,12 +222,26 @@ impl Config for PartialNodeConfig {
             public_key:                None,
             job_manager_interval_ms:   None,
             runtime_worker_threads:    None,
+            job_timeout_ms:            None,
+            gas_limit:                 None,
+            promise_timeout_ms:        None,
+            max_memory_pages:          None,
+            max_promise_generations:   None,
+            wasm_cache_path:           None,
+            db_path:                   None,
+            shared_db_namespaces:      None,
+            trace_jobs:                None,
+            wasm_binaries:             None,
+            wasm_capabilities:         None,
         }
     }
 
     fn overwrite_from_env(&mut self) {
         env_overwrite!(self.seda_chain_secret_key, "SEDA_CHAIN_SECRET_KEY");
         env_overwrite!(self.seda_secret_key, "SEDA_SECRET_KEY");
+        env_overwrite!(self.gas_limit, "SEDA_GAS_LIMIT", |g: String| Some(
+            g.parse().expect("Invalid gas limit specified.")
+        ));
     }
 }
 
@@ -152,6 +257,17 @@ pub struct NodeConfigInner {
     pub public_key:                String,
     pub job_manager_interval_ms:   u64,
     pub runtime_worker_threads:    usize,
+    pub job_timeout_ms:            u64,
+    pub gas_limit:                 u64,
+    pub promise_timeout_ms:        u64,
+    pub max_memory_pages:          u32,
+    pub max_promise_generations:   u32,
+    pub wasm_cache_path:           PathBuf,
+    pub db_path:                   PathBuf,
+    pub shared_db_namespaces:      Vec<String>,
+    pub trace_jobs:                bool,
+    pub wasm_binaries:             BTreeMap<String, WasmBinarySource>,
+    pub wasm_capabilities:         BTreeMap<String, Capabilities>,
 }
 
 impl NodeConfigInner {
@@ -168,9 +284,36 @@ impl NodeConfigInner {
             public_key:                String::new(),
             job_manager_interval_ms:   Self::JOB_MANAGER_INTERVAL_MS,
             runtime_worker_threads:    Self::RUNTIME_WORKER_THREADS,
+            job_timeout_ms:            Self::JOB_TIMEOUT_MS,
+            gas_limit:                 Self::GAS_LIMIT,
+            promise_timeout_ms:        Self::PROMISE_TIMEOUT_MS,
+            max_memory_pages:          Self::MAX_MEMORY_PAGES,
+            max_promise_generations:   Self::MAX_PROMISE_GENERATIONS,
+            wasm_cache_path:           "./wasm_cache".into(),
+            db_path:                   "./seda_db.db3".into(),
+            shared_db_namespaces:      Vec::new(),
+            trace_jobs:                false,
+            wasm_binaries:             Self::default_wasm_binaries(),
+            wasm_capabilities:         BTreeMap::new(),
         })
     }
 
+    pub fn default_wasm_binaries() -> BTreeMap<String, WasmBinarySource> {
+        BTreeMap::from([(
+            Self::CONSENSUS_BINARY.to_string(),
+            WasmBinarySource::Path(Self::CONSENSUS_BINARY_PATH.into()),
+        )])
+    }
+
+    /// The capabilities of a binary that runs in limited mode, see
+    /// [Capabilities::without_manifest] for a binary that has none configured.
+    pub fn capabilities(&self, program_name: &str) -> Capabilities {
+        self.wasm_capabilities
+            .get(program_name)
+            .cloned()
+            .unwrap_or_else(Capabilities::without_manifest)
+    }
+
     pub fn from_json_str(s: &str) -> NodeConfig {
         let this = serde_json::from_str(s).unwrap();
         Arc::new(this)
@@ -178,10 +321,33 @@ impl NodeConfigInner {
 }
 
 impl NodeConfigInner {
+    pub const CONSENSUS_BINARY: &str = "consensus";
+    #[cfg(debug_assertions)]
+    pub const CONSENSUS_BINARY_PATH: &str = "./target/wasm32-wasi/debug/consensus.wasm";
+    #[cfg(not(debug_assertions))]
+    pub const CONSENSUS_BINARY_PATH: &str = "./target/wasm32-wasi/release/consensus.wasm";
+    pub const DB_FILE: &str = "seda_db.db3";
     pub const DEPOSIT: u128 = 87 * 10_u128.pow(19);
     pub const GAS: u64 = 300_000_000_000_000;
+    pub const GAS_LIMIT: u64 = 10_000_000_000;
     pub const JOB_MANAGER_INTERVAL_MS: u64 = 10;
+    pub const JOB_TIMEOUT_MS: u64 = 60_000;
+    pub const MAX_MEMORY_PAGES: u32 = 1024;
+    pub const MAX_PROMISE_GENERATIONS: u32 = 1024;
+    pub const PROMISE_TIMEOUT_MS: u64 = 10_000;
     pub const RUNTIME_WORKER_THREADS: usize = 2;
+    pub const WASM_CACHE_DIR: &str = "wasm_cache";
 }
 
 pub type NodeConfig = Arc<NodeConfigInner>;
+
+/// Where the node loads a WASM binary from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WasmBinarySource {
+    /// The path to the WASM binary
+    Path(PathBuf),
+    /// The sha256 hash of a binary that was compiled into the WASM cache
+    /// before
+    Hash(String),
+}
@@ -1,4 +1,6 @@
-use bn254::format_pairing_check_values;
+use std::collections::HashSet;
+
+use bn254::{format_pairing_check_values, PublicKey};
 use near_sdk::near_bindgen;
 use near_sys::alt_bn128_pairing_check;
 
@@ -6,8 +8,11 @@ use crate::{MainchainContract, MainchainContractExt};
 
 #[near_bindgen]
 impl MainchainContract {
-    pub fn bn254_verify(&mut self, message: Vec<u8>, signature: Vec<u8>, public_key: Vec<u8>) -> bool {
-        let vals = format_pairing_check_values(message, signature, public_key).unwrap();
+    pub fn bn254_verify(&self, message: Vec<u8>, signature: Vec<u8>, public_key: Vec<u8>) -> bool {
+        let vals = match format_pairing_check_values(message, signature, public_key) {
+            Ok(vals) => vals,
+            Err(_) => return false,
+        };
 
         let res;
         unsafe {
@@ -16,4 +21,38 @@ impl MainchainContract {
 
         res == 1
     }
+
+    /// Verifies a signature aggregated from the signatures of a committee
+    /// against the compressed public keys of its members.
+    ///
+    /// Every public key must belong to a registered node, whose registration
+    /// proved possession of the key. Aggregating unregistered keys would let a
+    /// rogue key cancel out the others and forge the committee signature. A
+    /// key may only be given once, so a member can't count for several.
+    pub fn bn254_verify_aggregate(&self, message: Vec<u8>, signature: Vec<u8>, public_keys: Vec<Vec<u8>>) -> bool {
+        let unique_public_keys: HashSet<&Vec<u8>> = public_keys.iter().collect();
+        if unique_public_keys.len() != public_keys.len() {
+            return false;
+        }
+
+        if !public_keys
+            .iter()
+            .all(|public_key| self.nodes_by_bn254_public_key.contains_key(public_key))
+        {
+            return false;
+        }
+
+        let public_keys: Result<Vec<PublicKey>, _> = public_keys.into_iter().map(PublicKey::from_compressed).collect();
+        let aggregate_public_key = match public_keys {
+            Ok(public_keys) => public_keys
+                .into_iter()
+                .reduce(|aggregate, public_key| aggregate + public_key),
+            Err(_) => return false,
+        };
+
+        match aggregate_public_key.map(|aggregate_public_key| aggregate_public_key.to_compressed()) {
+            Some(Ok(aggregate_public_key)) => self.bn254_verify(message, signature, aggregate_public_key),
+            Some(Err(_)) | None => false,
+        }
+    }
 }
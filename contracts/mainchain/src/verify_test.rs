@@ -22,7 +22,7 @@ fn new_contract() -> MainchainContract {
 fn test_verify_signed_msg() {
     let context = get_context(false);
     testing_env!(context);
-    let mut contract = new_contract();
+    let contract = new_contract();
 
     // Public key
     let private_key = hex::decode("2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c").unwrap();
@@ -47,7 +47,7 @@ fn test_verify_signed_msg() {
 fn test_verify_aggregate_signatures() {
     let context = get_context(false);
     testing_env!(context);
-    let mut contract = new_contract();
+    let contract = new_contract();
 
     // Message
     let msg = hex::decode("73616d706c65").unwrap();
@@ -90,3 +90,77 @@ fn test_verify_aggregate_signatures() {
         "Aggregated signature verification failed"
     );
 }
+
+/// Test aggregate signature verification against the committee public keys
+#[test]
+fn test_verify_aggregate_committee_public_keys() {
+    let context = get_context(false);
+    testing_env!(context);
+    let mut contract = new_contract();
+
+    // Message
+    let msg = hex::decode("73616d706c65").unwrap();
+
+    let private_keys: Vec<PrivateKey> = [
+        "1ab1126ff2e37c6e6eddea943ccb3a48f83b380b856424ee552e113595525565",
+        "2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c",
+    ]
+    .iter()
+    .map(|private_key| PrivateKey::try_from(hex::decode(private_key).unwrap().as_ref()).unwrap())
+    .collect();
+    let public_keys: Vec<Vec<u8>> = private_keys
+        .iter()
+        .map(|private_key| PublicKey::from_private_key(private_key).to_compressed().unwrap())
+        .collect();
+    let agg_signature = private_keys
+        .iter()
+        .map(|private_key| ECDSA::sign(&msg, private_key).unwrap())
+        .reduce(|aggregate, signature| aggregate + signature)
+        .unwrap()
+        .to_compressed()
+        .unwrap();
+
+    // Keys of unregistered nodes are rejected
+    assert!(
+        !contract.bn254_verify_aggregate(msg.clone(), agg_signature.clone(), public_keys.clone()),
+        "Aggregated signature verified with unregistered public keys"
+    );
+
+    for (public_key, account_id) in public_keys.iter().zip(["bob_near", "alice_near"]) {
+        contract
+            .nodes_by_bn254_public_key
+            .insert(public_key, &account_id.parse().unwrap());
+    }
+
+    assert!(
+        contract.bn254_verify_aggregate(msg.clone(), agg_signature.clone(), public_keys.clone()),
+        "Aggregated signature verification failed"
+    );
+
+    // A missing committee member fails the verification
+    assert!(
+        !contract.bn254_verify_aggregate(msg.clone(), agg_signature.clone(), public_keys[..1].to_vec()),
+        "Aggregated signature verified with a missing public key"
+    );
+    assert!(
+        !contract.bn254_verify_aggregate(msg.clone(), agg_signature.clone(), vec![]),
+        "Aggregated signature verified without public keys"
+    );
+
+    // A repeated public key fails the verification
+    let repeated_public_keys = vec![public_keys[0].clone(), public_keys[1].clone(), public_keys[0].clone()];
+    assert!(
+        !contract.bn254_verify_aggregate(msg.clone(), agg_signature.clone(), repeated_public_keys),
+        "Aggregated signature verified with a repeated public key"
+    );
+
+    // An invalid public key fails the verification instead of panicking
+    let invalid_public_key = vec![1, 2, 3];
+    contract
+        .nodes_by_bn254_public_key
+        .insert(&invalid_public_key, &"carol_near".parse().unwrap());
+    assert!(
+        !contract.bn254_verify_aggregate(msg, agg_signature, vec![public_keys[0].clone(), invalid_public_key]),
+        "Aggregated signature verified with an invalid public key"
+    );
+}
@@ -0,0 +1,27 @@
+use actix::prelude::*;
+use seda_runtime::HostAdapter;
+use seda_runtime_sdk::p2p::P2PCommand;
+
+use super::App;
+use crate::Result;
+
+/// Broadcasts data to all the peers of the node.
+#[derive(Message)]
+#[rtype(result = "Result<()>")]
+pub struct BroadcastP2PMessage {
+    pub data: Vec<u8>,
+}
+
+impl<HA: HostAdapter> Handler<BroadcastP2PMessage> for App<HA> {
+    type Result = ResponseFuture<Result<()>>;
+
+    fn handle(&mut self, msg: BroadcastP2PMessage, _ctx: &mut Self::Context) -> Self::Result {
+        let p2p_command_sender_channel = self.p2p_command_sender_channel.clone();
+
+        Box::pin(async move {
+            p2p_command_sender_channel.send(P2PCommand::Broadcast(msg.data)).await?;
+
+            Ok(())
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
-use actix::{AsyncContext, Handler, Message};
-use seda_runtime::HostAdapter;
+use actix::{ActorFutureExt, AsyncContext, Handler, Message, WrapFuture};
+use seda_runtime::{CancellationToken, HostAdapter};
 
 use crate::{app::App, runtime_job::RuntimeJob};
 
@@ -30,9 +30,29 @@ impl<HA: HostAdapter> Handler<StartJobManager> for App<HA> {
 
     fn handle(&mut self, msg: StartJobManager, ctx: &mut Self::Context) -> Self::Result {
         let mut event_queue = self.event_queue.write();
-        let running_event_ids = self.running_event_ids.read();
-        if let Some(event) = event_queue.get_next(running_event_ids.as_slice()) {
-            self.runtime_worker.do_send(RuntimeJob { event });
+        let mut running_jobs = self.running_jobs.write();
+        let running_event_ids: Vec<String> = running_jobs.keys().cloned().collect();
+
+        if let Some(event) = event_queue.get_next(&running_event_ids) {
+            let event_id = event.id.clone();
+            let cancellation_token = CancellationToken::default();
+            running_jobs.insert(event_id.clone(), cancellation_token.clone());
+
+            let job = self.runtime_worker.send(RuntimeJob {
+                event,
+                cancellation_token,
+            });
+
+            // The event ID is free again once the job is done
+            ctx.spawn(job.into_actor(self).map(move |result, app, _| {
+                app.running_jobs.write().remove(&event_id);
+
+                if let Ok(Ok(job_result)) = result {
+                    if let Some(trace) = job_result.vm_result.trace {
+                        app.job_traces.write().insert(event_id, trace);
+                    }
+                }
+            }));
         }
 
         ctx.notify_later(msg, Duration::from_millis(StartJobManager::JOB_MANAGER_INTERVAL));
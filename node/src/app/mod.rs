@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use actix::prelude::*;
 use parking_lot::RwLock;
 use seda_config::{ChainConfigs, NodeConfig};
 use seda_p2p::DiscoveryStatus;
-use seda_runtime::HostAdapter;
+use seda_runtime::{CancellationToken, HostAdapter};
 use seda_runtime_sdk::{events::EventId, p2p::P2PCommand};
 use tokio::sync::mpsc::Sender;
 use tracing::info;
@@ -12,20 +12,29 @@ use tracing::info;
 use crate::{
     event_queue::EventQueue,
     host::{Host, SetAppAddress},
+    job_traces::JobTraces,
     rpc::JsonRpcServer,
     runtime_job::RuntimeWorker,
+    wasm_registry::WasmRegistry,
 };
 
+mod broadcast_p2p_message;
+pub use broadcast_p2p_message::BroadcastP2PMessage;
 mod job_manager;
 pub mod p2p_message_handler;
 mod shutdown;
 pub use shutdown::Shutdown;
 // Node Actor definition
 pub struct App<HA: HostAdapter> {
-    pub event_queue:       Arc<RwLock<EventQueue>>,
-    pub running_event_ids: Arc<RwLock<Vec<EventId>>>,
-    pub runtime_worker:    Addr<RuntimeWorker<HA>>,
-    pub rpc_server:        JsonRpcServer,
+    pub event_queue:                Arc<RwLock<EventQueue>>,
+    pub running_jobs:               Arc<RwLock<HashMap<EventId, CancellationToken>>>,
+    /// The trace of the last job of the most recent events, only kept when
+    /// the node config traces jobs
+    pub job_traces:                 Arc<RwLock<JobTraces>>,
+    pub runtime_worker:             Addr<RuntimeWorker<HA>>,
+    pub rpc_server:                 JsonRpcServer,
+    /// Used by the WASM binaries to broadcast to the peers of the node
+    pub p2p_command_sender_channel: Sender<P2PCommand>,
 }
 
 impl<HA: HostAdapter> App<HA> {
@@ -36,15 +45,16 @@ impl<HA: HostAdapter> App<HA> {
         p2p_command_sender_channel: Sender<P2PCommand>,
         disocvery_status: DiscoveryStatus,
     ) -> Self {
-        // Have to clone beforehand in order for the variable to be moved. (We also need
-        // the same sender for the RPC)
-        let p2p_command_sender_channel_clone = p2p_command_sender_channel.clone();
+        let running_jobs: Arc<RwLock<HashMap<EventId, CancellationToken>>> = Default::default();
+        let job_traces = Arc::new(RwLock::new(JobTraces::new(JobTraces::CAPACITY)));
+        let wasm_registry = WasmRegistry::load(&node_config).expect("Failed to load the WASM binaries");
 
         let runtime_worker = SyncArbiter::start(node_config.runtime_worker_threads, move || RuntimeWorker {
-            runtime:                    None,
-            node_config:                node_config.clone(),
-            chain_configs:              chain_configs.clone(),
-            p2p_command_sender_channel: p2p_command_sender_channel_clone.clone(),
+            runtimes:      Default::default(),
+            executor:      None,
+            wasm_registry: wasm_registry.clone(),
+            node_config:   node_config.clone(),
+            chain_configs: chain_configs.clone(),
         });
 
         let rpc_server = JsonRpcServer::start(
@@ -52,15 +62,19 @@ impl<HA: HostAdapter> App<HA> {
             rpc_server_address,
             p2p_command_sender_channel.clone(),
             disocvery_status.clone(),
+            running_jobs.clone(),
+            job_traces.clone(),
         )
         .await
         .expect("Error starting jsonrpsee server");
 
         App {
             event_queue: Default::default(),
-            running_event_ids: Default::default(),
+            running_jobs,
+            job_traces,
             runtime_worker,
             rpc_server,
+            p2p_command_sender_channel,
         }
     }
 }
@@ -1,9 +1,10 @@
 use actix::Addr;
 use seda_runtime_sdk::{
     events::{Event, EventData},
-    p2p::P2PMessage,
+    p2p::{P2PEnvelope, P2PMessage},
 };
 use tokio::sync::mpsc::Receiver;
+use tracing::warn;
 
 use super::App;
 use crate::{event_queue_handler::AddEventToQueue, host::RuntimeAdapter};
@@ -24,10 +25,23 @@ impl P2PMessageHandler {
     pub async fn listen(&mut self) {
         loop {
             if let Some(message) = self.p2p_message_receiver.recv().await {
+                // The message runs at the consensus time of the event that broadcast it
+                let envelope: P2PEnvelope = match serde_json::from_slice(&message.data) {
+                    Ok(envelope) => envelope,
+                    Err(err) => {
+                        warn!("Dropping a P2P message without a valid envelope: {err}");
+                        continue;
+                    }
+                };
+
                 self.app_addr.do_send(AddEventToQueue {
                     event: Event {
-                        id:   "p2p-message".to_string(),
-                        data: EventData::P2PMessage(message),
+                        id:        "p2p-message".to_string(),
+                        data:      EventData::P2PMessage(P2PMessage {
+                            source: message.source,
+                            data:   envelope.data,
+                        }),
+                        timestamp: envelope.timestamp,
                     },
                 });
             }
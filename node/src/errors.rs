@@ -1,4 +1,6 @@
+use seda_runtime_sdk::p2p::P2PCommand;
 use thiserror::Error;
+use tokio::sync::mpsc::error::SendError;
 
 #[derive(Error, Debug)]
 pub enum NodeError {
@@ -8,6 +10,8 @@ pub enum NodeError {
     MailboxError(#[from] actix::MailboxError),
     #[error(transparent)]
     P2PError(#[from] seda_p2p::P2PAdapterError),
+    #[error("P2P Command Channel Error: {0}")]
+    P2PCommandChannelError(#[from] SendError<P2PCommand>),
     #[error("Reqwest Error: {0}")]
     ReqwestError(#[from] reqwest::Error),
     #[error("Rusqlite Error: {0}")]
@@ -16,6 +20,15 @@ pub enum NodeError {
     ChainAdapterError(#[from] seda_chains::ChainAdapterError),
     #[error("Missing app actor address in host adapter, was the node booted?")]
     MissingAppActorAddress,
+    #[error("Failed to load WASM binary `{name}`: {error}")]
+    WasmBinaryError {
+        name:  String,
+        error: seda_runtime::RuntimeError,
+    },
+    #[error("Missing WASM binary `{0}` in the node config")]
+    MissingWasmBinary(String),
+    #[error("Invalid node secret key: {0}")]
+    InvalidSecretKey(String),
 }
 
 pub type Result<T, E = NodeError> = core::result::Result<T, E>;
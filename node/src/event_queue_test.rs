@@ -7,8 +7,9 @@ fn add_item_to_event_queue() {
     let mut queue = EventQueue::default();
 
     queue.add(Event {
-        id:   "test-id".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     let item = queue.get_next(&[]).unwrap();
@@ -21,13 +22,15 @@ fn get_item_with_skip() {
     let mut queue = EventQueue::default();
 
     queue.add(Event {
-        id:   "test-id".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     queue.add(Event {
-        id:   "test-id-2".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id-2".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     let item = queue.get_next(&["test-id".to_string()]).unwrap();
@@ -40,13 +43,15 @@ fn get_item_should_empty_queue() {
     let mut queue = EventQueue::default();
 
     queue.add(Event {
-        id:   "test-id".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     queue.add(Event {
-        id:   "test-id-2".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id-2".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     let item = queue.get_next(&[]).unwrap();
@@ -63,13 +68,15 @@ fn get_item_should_empty_queue_with_skip() {
     let mut queue = EventQueue::default();
 
     queue.add(Event {
-        id:   "test-id".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     queue.add(Event {
-        id:   "test-id-2".to_string(),
-        data: EventData::ChainTick,
+        id:        "test-id-2".to_string(),
+        data:      EventData::ChainTick,
+        timestamp: 0,
     });
 
     let item = queue.get_next(&["test-id".to_string()]).unwrap();
@@ -1,15 +1,57 @@
-use std::{fs::File, io::Write};
+use std::{fs, sync::Arc};
 
 use bn254::PrivateKey;
-use seda_config::NodeConfig;
-
-pub fn generate_secret_key(config: NodeConfig) {
-    // Checks if there is a SEDA_SECRET_KEY env variable
-    // If not, it generates a new random one and saves it into a file
-    if std::env::var("SEDA_SECRET_KEY").is_err() && config.seda_secret_key.is_empty() {
-        let rng = &mut rand::thread_rng();
-        let sk = PrivateKey::random(rng);
-        let mut file = File::create(config.seda_secret_key_file_path.clone()).expect("Unable to create file");
-        writeln!(file, "{:?}", sk.to_bytes().expect("couldn't serialize sk")).expect("Unable to write secret key");
-    }
+use seda_config::{NodeConfig, NodeConfigInner};
+
+use crate::{NodeError, Result};
+
+/// Loads the secret key of the node into the config. A key set in the config
+/// or with the `SEDA_SECRET_KEY` env variable is kept, otherwise it's read from
+/// the key file, which is written with a new random key the first time. Fails
+/// when the key isn't a valid hex encoded bn254 private key.
+pub fn generate_secret_key(config: NodeConfig) -> Result<NodeConfig> {
+    let seda_secret_key = if !config.seda_secret_key.is_empty() {
+        config.seda_secret_key.clone()
+    } else {
+        match fs::read_to_string(&config.seda_secret_key_file_path) {
+            Ok(contents) => match parse_legacy_secret_key(contents.trim()) {
+                // Older nodes wrote the key as a debug formatted list of bytes
+                Some(bytes) => {
+                    let sk = hex::encode(bytes);
+                    validate_secret_key(&sk)?;
+                    fs::write(&config.seda_secret_key_file_path, format!("{sk}\n"))
+                        .expect("Unable to migrate secret key");
+                    sk
+                }
+                None => contents.trim().to_string(),
+            },
+            Err(_) => {
+                let rng = &mut rand::thread_rng();
+                let sk = PrivateKey::random(rng);
+                let sk = hex::encode(sk.to_bytes().expect("couldn't serialize sk"));
+                fs::write(&config.seda_secret_key_file_path, format!("{sk}\n")).expect("Unable to write secret key");
+                sk
+            }
+        }
+    };
+    validate_secret_key(&seda_secret_key)?;
+
+    Ok(Arc::new(NodeConfigInner {
+        seda_secret_key,
+        ..(*config).clone()
+    }))
+}
+
+/// Parses a key in the `[1, 2, 3]` format older nodes wrote.
+fn parse_legacy_secret_key(contents: &str) -> Option<Vec<u8>> {
+    let bytes = contents.strip_prefix('[')?.strip_suffix(']')?;
+
+    bytes.split(',').map(|byte| byte.trim().parse().ok()).collect()
+}
+
+fn validate_secret_key(secret_key: &str) -> Result<()> {
+    let bytes = hex::decode(secret_key).map_err(|error| NodeError::InvalidSecretKey(error.to_string()))?;
+    PrivateKey::try_from(bytes.as_slice()).map_err(|error| NodeError::InvalidSecretKey(error.to_string()))?;
+
+    Ok(())
 }
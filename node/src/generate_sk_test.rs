@@ -0,0 +1,70 @@
+use std::{env, fs, path::PathBuf, sync::Arc};
+
+use seda_config::NodeConfigInner;
+
+use crate::{generate_sk::generate_secret_key, NodeError};
+
+const SECRET_KEY: &str = "2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c";
+
+fn key_file(name: &str, contents: Option<&str>) -> PathBuf {
+    let path = env::temp_dir().join(format!("seda_test_{name}_sk"));
+    let _ = fs::remove_file(&path);
+    if let Some(contents) = contents {
+        fs::write(&path, contents).unwrap();
+    }
+
+    path
+}
+
+fn load(seda_secret_key: &str, seda_secret_key_file_path: PathBuf) -> crate::Result<String> {
+    let config = Arc::new(NodeConfigInner {
+        seda_secret_key: seda_secret_key.to_string(),
+        seda_secret_key_file_path,
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+
+    Ok(generate_secret_key(config)?.seda_secret_key.clone())
+}
+
+#[test]
+fn config_key_is_kept() {
+    let path = key_file("config", None);
+
+    assert_eq!(load(SECRET_KEY, path.clone()).unwrap(), SECRET_KEY);
+    assert!(!path.exists());
+}
+
+#[test]
+fn key_file_is_read() {
+    let path = key_file("hex", Some(&format!("{SECRET_KEY}\n")));
+
+    assert_eq!(load("", path).unwrap(), SECRET_KEY);
+}
+
+#[test]
+fn missing_key_file_is_written() {
+    let path = key_file("missing", None);
+
+    let secret_key = load("", path.clone()).unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap().trim(), secret_key);
+}
+
+#[test]
+fn legacy_key_file_is_migrated() {
+    let legacy = format!("{:?}\n", hex::decode(SECRET_KEY).unwrap());
+    let path = key_file("legacy", Some(&legacy));
+
+    assert_eq!(load("", path.clone()).unwrap(), SECRET_KEY);
+    assert_eq!(fs::read_to_string(path).unwrap().trim(), SECRET_KEY);
+}
+
+#[test]
+fn invalid_key_fails() {
+    let path = key_file("invalid", Some("not a key\n"));
+
+    assert!(matches!(load("", path), Err(NodeError::InvalidSecretKey(_))));
+    assert!(matches!(
+        load("node_secret", PathBuf::new()),
+        Err(NodeError::InvalidSecretKey(_))
+    ));
+}
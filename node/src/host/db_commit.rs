@@ -0,0 +1,23 @@
+use actix::prelude::*;
+use seda_runtime::{DatabaseWrite, HostAdapter};
+
+use super::storage;
+use crate::{Host, Result};
+
+#[derive(Message)]
+#[rtype(result = "Result<()>")]
+pub struct DatabaseCommit {
+    pub writes: Vec<DatabaseWrite>,
+}
+
+impl<HA: HostAdapter> Handler<DatabaseCommit> for Host<HA> {
+    type Result = ResponseActFuture<Self, Result<()>>;
+
+    fn handle(&mut self, msg: DatabaseCommit, _ctx: &mut Self::Context) -> Self::Result {
+        let db_conn = self.db_conn.clone();
+
+        let fut = async move { db_conn.call(move |conn| storage::commit(conn, &msg.writes)).await };
+
+        Box::pin(fut.into_actor(self))
+    }
+}
@@ -0,0 +1,29 @@
+use actix::prelude::*;
+use seda_runtime::HostAdapter;
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+use crate::{Host, Result};
+
+#[derive(Message, Serialize, Deserialize)]
+#[rtype(result = "Result<()>")]
+pub struct DatabaseDelete {
+    pub namespace: String,
+    pub key:       String,
+}
+
+impl<HA: HostAdapter> Handler<DatabaseDelete> for Host<HA> {
+    type Result = ResponseActFuture<Self, Result<()>>;
+
+    fn handle(&mut self, msg: DatabaseDelete, _ctx: &mut Self::Context) -> Self::Result {
+        let db_conn = self.db_conn.clone();
+
+        let fut = async move {
+            db_conn
+                .call(move |conn| storage::delete(conn, &msg.namespace, &msg.key))
+                .await
+        };
+
+        Box::pin(fut.into_actor(self))
+    }
+}
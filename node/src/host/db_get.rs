@@ -2,35 +2,26 @@ use actix::prelude::*;
 use seda_runtime::HostAdapter;
 use serde::{Deserialize, Serialize};
 
-use crate::{Host, NodeError, Result};
+use super::storage;
+use crate::{Host, Result};
 
 #[derive(Message, Serialize, Deserialize)]
-#[rtype(result = "Result<Option<String>>")]
+#[rtype(result = "Result<Option<Vec<u8>>>")]
 pub struct DatabaseGet {
-    pub key: String,
+    pub namespace: String,
+    pub key:       String,
 }
 
 impl<HA: HostAdapter> Handler<DatabaseGet> for Host<HA> {
-    type Result = ResponseActFuture<Self, Result<Option<String>>>;
+    type Result = ResponseActFuture<Self, Result<Option<Vec<u8>>>>;
 
     fn handle(&mut self, msg: DatabaseGet, _ctx: &mut Self::Context) -> Self::Result {
         let db_conn = self.db_conn.clone();
 
         let fut = async move {
-            let value = db_conn
-                .call(move |conn| {
-                    let mut stmt = conn.prepare("SELECT value FROM data WHERE key = ?1")?;
-                    let mut retrieved: Option<String> = None;
-
-                    stmt.query_row([msg.key], |row| {
-                        retrieved = row.get(0)?;
-                        Ok(())
-                    })?;
-                    Ok::<_, NodeError>(retrieved)
-                })
-                .await?;
-
-            Ok(value)
+            db_conn
+                .call(move |conn| storage::get(conn, &msg.namespace, &msg.key))
+                .await
         };
 
         Box::pin(fut.into_actor(self))
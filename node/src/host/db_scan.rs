@@ -0,0 +1,30 @@
+use actix::prelude::*;
+use seda_runtime::HostAdapter;
+use seda_runtime_sdk::DatabaseEntry;
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+use crate::{Host, Result};
+
+#[derive(Message, Serialize, Deserialize)]
+#[rtype(result = "Result<Vec<DatabaseEntry>>")]
+pub struct DatabaseScan {
+    pub namespace: String,
+    pub prefix:    String,
+}
+
+impl<HA: HostAdapter> Handler<DatabaseScan> for Host<HA> {
+    type Result = ResponseActFuture<Self, Result<Vec<DatabaseEntry>>>;
+
+    fn handle(&mut self, msg: DatabaseScan, _ctx: &mut Self::Context) -> Self::Result {
+        let db_conn = self.db_conn.clone();
+
+        let fut = async move {
+            db_conn
+                .call(move |conn| storage::scan(conn, &msg.namespace, &msg.prefix))
+                .await
+        };
+
+        Box::pin(fut.into_actor(self))
+    }
+}
@@ -1,15 +1,16 @@
 use actix::prelude::*;
-use rusqlite::params;
 use seda_runtime::HostAdapter;
 use serde::{Deserialize, Serialize};
 
-use crate::{Host, NodeError, Result};
+use super::storage;
+use crate::{Host, Result};
 
 #[derive(Message, Serialize, Deserialize)]
 #[rtype(result = "Result<()>")]
 pub struct DatabaseSet {
-    pub key:   String,
-    pub value: String,
+    pub namespace: String,
+    pub key:       String,
+    pub value:     Vec<u8>,
 }
 
 impl<HA: HostAdapter> Handler<DatabaseSet> for Host<HA> {
@@ -20,17 +21,8 @@ impl<HA: HostAdapter> Handler<DatabaseSet> for Host<HA> {
 
         let fut = async move {
             db_conn
-                .call(move |conn| {
-                    conn.execute(
-                        "INSERT INTO data (key, value) VALUES (?1, ?2)",
-                        params![msg.key, msg.value],
-                    )?;
-
-                    Ok::<_, NodeError>(())
-                })
-                .await?;
-
-            Ok(())
+                .call(move |conn| storage::set(conn, &msg.namespace, &msg.key, &msg.value))
+                .await
         };
 
         Box::pin(fut.into_actor(self))
@@ -1,21 +1,65 @@
+use std::{collections::BTreeMap, time::Duration};
+
 use actix::prelude::*;
+use reqwest::{redirect::Policy, Method};
 use seda_runtime::HostAdapter;
+use seda_runtime_sdk::{HttpAction, HttpMethod, HttpResponse};
 use serde::{Deserialize, Serialize};
 
-use super::Host;
+use crate::{Host, Result};
 
 #[derive(Message, Serialize, Deserialize)]
-#[rtype(result = "String")]
+#[rtype(result = "Result<HttpResponse>")]
 pub struct HttpFetch {
-    pub url: String,
+    pub action: HttpAction,
+}
+
+impl HttpFetch {
+    pub async fn fetch(self) -> Result<HttpResponse> {
+        let method = match self.action.method {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Post => Method::POST,
+            HttpMethod::Put => Method::PUT,
+            HttpMethod::Patch => Method::PATCH,
+            HttpMethod::Delete => Method::DELETE,
+            HttpMethod::Head => Method::HEAD,
+            HttpMethod::Options => Method::OPTIONS,
+        };
+
+        // A redirect could lead to a host the capabilities of the binary don't
+        // allow, so the binary gets the redirect response to follow itself
+        let client = reqwest::Client::builder().redirect(Policy::none()).build()?;
+        let mut request = client.request(method, &self.action.url);
+        for (name, value) in &self.action.headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = self.action.body {
+            request = request.body(body);
+        }
+        if let Some(timeout_ms) = self.action.timeout_ms {
+            request = request.timeout(Duration::from_millis(timeout_ms));
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let mut headers = BTreeMap::<String, String>::new();
+        for (name, value) in response.headers() {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            headers
+                .entry(name.to_string())
+                .and_modify(|existing| *existing = format!("{existing}, {value}"))
+                .or_insert_with(|| value.to_string());
+        }
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse { status, headers, body })
+    }
 }
 
 impl<HA: HostAdapter> Handler<HttpFetch> for Host<HA> {
-    type Result = ResponseActFuture<Self, String>;
+    type Result = ResponseActFuture<Self, Result<HttpResponse>>;
 
     fn handle(&mut self, msg: HttpFetch, _ctx: &mut Self::Context) -> Self::Result {
-        let fut = async { reqwest::get(msg.url).await.unwrap().text().await.unwrap() };
-
-        Box::pin(fut.into_actor(self))
+        Box::pin(msg.fetch().into_actor(self))
     }
 }
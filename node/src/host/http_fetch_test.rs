@@ -0,0 +1,35 @@
+use seda_runtime_sdk::HttpAction;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use super::http_fetch::HttpFetch;
+
+#[tokio::test]
+async fn fetch_does_not_follow_redirects() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request).await.unwrap();
+        stream
+            .write_all(b"HTTP/1.1 302 Found\r\nLocation: http://not-allowed.org/\r\nContent-Length: 0\r\n\r\n")
+            .await
+            .unwrap();
+    });
+
+    let response = HttpFetch {
+        action: HttpAction {
+            url: format!("http://{addr}/"),
+            ..Default::default()
+        },
+    }
+    .fetch()
+    .await
+    .unwrap();
+
+    assert_eq!(response.status, 302);
+    assert_eq!(response.headers.get("location").unwrap(), "http://not-allowed.org/");
+}
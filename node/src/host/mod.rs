@@ -1,12 +1,34 @@
+use std::path::Path;
+
+mod db_commit;
+pub use db_commit::*;
+
+mod db_delete;
+pub use db_delete::*;
+
 mod db_get;
 pub use db_get::*;
 
+mod db_scan;
+pub use db_scan::*;
+
 mod db_set;
 pub use db_set::*;
 
+mod storage;
+
+#[cfg(test)]
+#[path = ""]
+pub mod test {
+    use super::*;
+
+    mod http_fetch_test;
+    mod storage_test;
+}
+
 mod http_fetch;
 pub use http_fetch::HttpFetch;
-use rusqlite::params;
+use seda_config::NodeConfigInner;
 use seda_runtime::HostAdapter;
 use tokio_rusqlite::Connection;
 
@@ -19,6 +41,9 @@ pub use chain_view::ChainView;
 mod trigger_event;
 pub use trigger_event::TriggerEvent;
 
+mod p2p_broadcast;
+pub use p2p_broadcast::P2PBroadcast;
+
 mod runtime_host;
 use actix::prelude::*;
 use futures::executor;
@@ -27,40 +52,31 @@ pub use runtime_host::*;
 mod set_app_addr;
 pub use set_app_addr::*;
 
-use crate::{app::App, NodeError};
+use crate::{app::App, Result};
 
 pub struct Host<HA: HostAdapter> {
     db_conn:        Connection,
     app_actor_addr: Option<Addr<App<HA>>>,
 }
 
+impl<HA: HostAdapter> Host<HA> {
+    /// Opens the node database, creating it when it doesn't exist yet.
+    pub async fn new(db_path: &Path) -> Result<Self> {
+        let db_conn = Connection::open(db_path).await?;
+        db_conn.call(storage::init).await?;
+
+        Ok(Host {
+            db_conn,
+            app_actor_addr: None,
+        })
+    }
+}
+
+/// Only used when the host is started from the registry before the node
+/// registered one with the configured database path.
 impl<HA: HostAdapter> Default for Host<HA> {
     fn default() -> Self {
-        executor::block_on(async move {
-            let db_conn = Connection::open("./seda_db.db3").await.expect("Couldn't open db conn");
-
-            db_conn
-                .call(|db_conn| {
-                    db_conn
-                        .execute(
-                            "CREATE TABLE IF NOT EXISTS data (
-                                key TEXT,
-                                value TEXT NOT NULL
-                            )",
-                            params![],
-                        )
-                        .expect("couldn't create db table");
-
-                    Ok::<_, NodeError>(())
-                })
-                .await
-                .expect("Couldn't execute db call");
-
-            Host {
-                db_conn,
-                app_actor_addr: None,
-            }
-        })
+        executor::block_on(Self::new(Path::new(NodeConfigInner::DB_FILE))).expect("Couldn't open the node database")
     }
 }
 
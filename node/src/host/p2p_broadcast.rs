@@ -0,0 +1,29 @@
+//! Communication layer between App & Host adapter
+//! We send a message to broadcast data to the host actor
+//! which redirects the message to the app actor, the owner of the P2P
+//! command channel
+use actix::prelude::*;
+use seda_runtime::HostAdapter;
+use serde::{Deserialize, Serialize};
+
+use crate::{app::BroadcastP2PMessage, Host, NodeError::MissingAppActorAddress, Result};
+
+#[derive(Message, Serialize, Deserialize)]
+#[rtype(result = "Result<()>")]
+pub struct P2PBroadcast {
+    pub data: Vec<u8>,
+}
+
+impl<HA: HostAdapter> Handler<P2PBroadcast> for Host<HA> {
+    type Result = ResponseFuture<Result<()>>;
+
+    fn handle(&mut self, msg: P2PBroadcast, _ctx: &mut Self::Context) -> Self::Result {
+        let app_actor = self.app_actor_addr.clone();
+
+        Box::pin(async move {
+            let app_actor = app_actor.ok_or(MissingAppActorAddress)?;
+
+            app_actor.send(BroadcastP2PMessage { data: msg.data }).await?
+        })
+    }
+}
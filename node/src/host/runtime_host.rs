@@ -1,67 +1,171 @@
 use actix::prelude::*;
 use seda_chains::{AnotherChain, ChainAdapterTrait, Client, NearChain};
 use seda_config::{ChainConfigs, NodeConfig};
-use seda_runtime::HostAdapter;
-use seda_runtime_sdk::{events::Event, Chain};
-
-use crate::{ChainCall, ChainView, DatabaseGet, DatabaseSet, Host, HttpFetch, NodeError, Result, TriggerEvent};
+use seda_runtime::{
+    Adapter,
+    ChainInteractorAdapter,
+    DatabaseAdapter,
+    DatabaseWrite,
+    EventSinkAdapter,
+    HttpAdapter,
+    P2PAdapter,
+};
+use seda_runtime_sdk::{events::Event, Chain, DatabaseEntry, HttpAction, HttpResponse};
+
+use crate::{
+    ChainCall,
+    ChainView,
+    DatabaseCommit,
+    DatabaseDelete,
+    DatabaseGet,
+    DatabaseScan,
+    DatabaseSet,
+    Host,
+    HttpFetch,
+    NodeError,
+    P2PBroadcast,
+    Result,
+    TriggerEvent,
+};
 
 /// A communication layer between Actix and the runtime
+#[derive(Adapter)]
+#[adapter(
+    error = NodeError,
+    database = HostDatabase,
+    http = HostHttp,
+    chain_interactor = HostChainInteractor,
+    event_sink = HostEventSink,
+    p2p = HostP2P
+)]
 pub struct RuntimeAdapter {
-    pub chains_config:  ChainConfigs,
-    pub another_client: Client,
-    pub near_client:    Client,
+    pub database:         HostDatabase,
+    pub http:             HostHttp,
+    pub chain_interactor: HostChainInteractor,
+    pub event_sink:       HostEventSink,
+    pub p2p:              HostP2P,
 }
 
+/// The node database, owned by the host actor
+pub struct HostDatabase;
+
 #[async_trait::async_trait]
-impl HostAdapter for RuntimeAdapter {
+impl DatabaseAdapter for HostDatabase {
     type Error = NodeError;
 
-    async fn new(config: ChainConfigs) -> Result<Self> {
-        Ok(Self {
-            another_client: Client::Another(AnotherChain::new_client(&config.another)?),
-            near_client:    Client::Near(NearChain::new_client(&config.near)?),
-            chains_config:  config,
-        })
-    }
-
-    fn select_client_from_chain(&self, chain: Chain) -> Client {
-        match chain {
-            Chain::Another => self.another_client.clone(),
-            Chain::Near => self.near_client.clone(),
-        }
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
     }
 
-    async fn db_get(&self, key: &str) -> Result<Option<String>> {
-        let host_actor = Host::<Self>::from_registry();
+    async fn get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
 
-        let result = host_actor.send(DatabaseGet { key: key.to_string() }).await??;
+        let result = host_actor
+            .send(DatabaseGet {
+                namespace: namespace.to_string(),
+                key:       key.to_string(),
+            })
+            .await??;
 
         Ok(result)
     }
 
-    async fn db_set(&self, key: &str, value: &str) -> Result<()> {
-        let host_actor = Host::<Self>::from_registry();
+    async fn set(&self, namespace: &str, key: &str, value: &[u8]) -> Result<()> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
 
         host_actor
             .send(DatabaseSet {
-                key:   key.to_string(),
-                value: value.to_string(),
+                namespace: namespace.to_string(),
+                key:       key.to_string(),
+                value:     value.to_vec(),
+            })
+            .await??;
+
+        Ok(())
+    }
+
+    async fn delete(&self, namespace: &str, key: &str) -> Result<()> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
+
+        host_actor
+            .send(DatabaseDelete {
+                namespace: namespace.to_string(),
+                key:       key.to_string(),
+            })
+            .await??;
+
+        Ok(())
+    }
+
+    async fn scan(&self, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
+
+        let result = host_actor
+            .send(DatabaseScan {
+                namespace: namespace.to_string(),
+                prefix:    prefix.to_string(),
             })
             .await??;
 
+        Ok(result)
+    }
+
+    async fn commit(&self, writes: Vec<DatabaseWrite>) -> Result<()> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
+
+        host_actor.send(DatabaseCommit { writes }).await??;
+
         Ok(())
     }
+}
+
+pub struct HostHttp;
+
+#[async_trait::async_trait]
+impl HttpAdapter for HostHttp {
+    type Error = NodeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
+    }
 
-    async fn http_fetch(&self, url: &str) -> Result<String> {
-        let host_actor = Host::<Self>::from_registry();
+    async fn fetch(&self, action: HttpAction) -> Result<HttpResponse> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
 
-        let result = host_actor.send(HttpFetch { url: url.to_string() }).await?;
+        let result = host_actor.send(HttpFetch { action }).await??;
 
         Ok(result)
     }
+}
+
+pub struct HostChainInteractor {
+    pub chains_config:  ChainConfigs,
+    pub another_client: Client,
+    pub near_client:    Client,
+}
+
+impl HostChainInteractor {
+    fn select_client_from_chain(&self, chain: Chain) -> Client {
+        match chain {
+            Chain::Another => self.another_client.clone(),
+            Chain::Near => self.near_client.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainInteractorAdapter for HostChainInteractor {
+    type Error = NodeError;
+
+    async fn new(config: &ChainConfigs) -> Result<Self> {
+        Ok(Self {
+            another_client: Client::Another(AnotherChain::new_client(&config.another)?),
+            near_client:    Client::Near(NearChain::new_client(&config.near)?),
+            chains_config:  config.clone(),
+        })
+    }
 
-    async fn chain_call(
+    async fn call(
         &self,
         chain: Chain,
         contract_id: &str,
@@ -70,7 +174,7 @@ impl HostAdapter for RuntimeAdapter {
         deposit: u128,
         node_config: NodeConfig,
     ) -> Result<Vec<u8>> {
-        let host_actor = Host::<Self>::from_registry();
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
         let client = self.select_client_from_chain(chain);
         let result = host_actor
             .send(ChainCall {
@@ -88,8 +192,8 @@ impl HostAdapter for RuntimeAdapter {
         Ok(result)
     }
 
-    async fn chain_view(&self, chain: Chain, contract_id: &str, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>> {
-        let host_actor = Host::<Self>::from_registry();
+    async fn view(&self, chain: Chain, contract_id: &str, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
         let client = self.select_client_from_chain(chain);
         let result = host_actor
             .send(ChainView {
@@ -103,11 +207,41 @@ impl HostAdapter for RuntimeAdapter {
 
         Ok(result)
     }
+}
+
+/// Adds the triggered events to the event queue of the app
+pub struct HostEventSink;
+
+#[async_trait::async_trait]
+impl EventSinkAdapter for HostEventSink {
+    type Error = NodeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
+    }
 
-    async fn trigger_event(&self, event: Event) -> Result<()> {
-        let host_actor = Host::<Self>::from_registry();
+    async fn trigger(&self, event: Event) -> Result<()> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
         host_actor.send(TriggerEvent { event }).await??;
 
         Ok(())
     }
 }
+
+pub struct HostP2P;
+
+#[async_trait::async_trait]
+impl P2PAdapter for HostP2P {
+    type Error = NodeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
+    }
+
+    async fn broadcast(&self, data: Vec<u8>) -> Result<()> {
+        let host_actor = Host::<RuntimeAdapter>::from_registry();
+        host_actor.send(P2PBroadcast { data }).await??;
+
+        Ok(())
+    }
+}
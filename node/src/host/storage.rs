@@ -0,0 +1,105 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use seda_config::NodeConfigInner;
+use seda_runtime::DatabaseWrite;
+use seda_runtime_sdk::DatabaseEntry;
+
+use crate::Result;
+
+/// Creates the key-value table, moving the values of the old `data` table
+/// into it the first time a node opens an existing database.
+pub fn init(conn: &mut Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS kv_store (
+            namespace TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value BLOB NOT NULL,
+            PRIMARY KEY (namespace, key)
+        )",
+        params![],
+    )?;
+
+    let has_legacy_table: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'data')",
+        params![],
+        |row| row.get(0),
+    )?;
+    if has_legacy_table {
+        // The old table allowed duplicate keys, the most recently written value
+        // wins. Only the consensus binary could write to it.
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO kv_store (namespace, key, value)
+                SELECT ?1, key, CAST(value AS BLOB) FROM data WHERE key IS NOT NULL ORDER BY rowid",
+            params![NodeConfigInner::CONSENSUS_BINARY],
+        )?;
+        tx.execute("DROP TABLE data", params![])?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+pub fn get(conn: &Connection, namespace: &str, key: &str) -> Result<Option<Vec<u8>>> {
+    let value = conn
+        .query_row(
+            "SELECT value FROM kv_store WHERE namespace = ?1 AND key = ?2",
+            params![namespace, key],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    Ok(value)
+}
+
+pub fn set(conn: &Connection, namespace: &str, key: &str, value: &[u8]) -> Result<()> {
+    conn.execute(
+        "INSERT INTO kv_store (namespace, key, value) VALUES (?1, ?2, ?3)
+            ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value",
+        params![namespace, key, value],
+    )?;
+
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, namespace: &str, key: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM kv_store WHERE namespace = ?1 AND key = ?2",
+        params![namespace, key],
+    )?;
+
+    Ok(())
+}
+
+/// Returns the entries of every key in the namespace that starts with the
+/// prefix, ordered by key.
+pub fn scan(conn: &Connection, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT key, value FROM kv_store
+            WHERE namespace = ?1 AND substr(key, 1, length(?2)) = ?2 ORDER BY key",
+    )?;
+    let entries = stmt
+        .query_map(params![namespace, prefix], |row| {
+            Ok(DatabaseEntry {
+                key:   row.get(0)?,
+                value: row.get(1)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    Ok(entries)
+}
+
+/// Applies all the writes in one transaction, either all of them end up in the
+/// database or none of them.
+pub fn commit(conn: &mut Connection, writes: &[DatabaseWrite]) -> Result<()> {
+    let tx = conn.transaction()?;
+    for write in writes {
+        match &write.value {
+            Some(value) => set(&tx, &write.namespace, &write.key, value)?,
+            None => delete(&tx, &write.namespace, &write.key)?,
+        }
+    }
+    tx.commit()?;
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use rusqlite::{params, Connection};
+use seda_runtime::DatabaseWrite;
+use seda_runtime_sdk::DatabaseEntry;
+
+use super::storage;
+
+fn open() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    storage::init(&mut conn).unwrap();
+    conn
+}
+
+#[test]
+fn set_overwrites_value() {
+    let conn = open();
+
+    storage::set(&conn, "consensus", "key", b"first").unwrap();
+    storage::set(&conn, "consensus", "key", b"second").unwrap();
+
+    assert_eq!(
+        storage::get(&conn, "consensus", "key").unwrap(),
+        Some(b"second".to_vec())
+    );
+}
+
+#[test]
+fn get_missing_key() {
+    let conn = open();
+
+    assert_eq!(storage::get(&conn, "consensus", "missing").unwrap(), None);
+}
+
+#[test]
+fn delete_key() {
+    let conn = open();
+
+    storage::set(&conn, "consensus", "key", b"value").unwrap();
+    storage::delete(&conn, "consensus", "key").unwrap();
+
+    assert_eq!(storage::get(&conn, "consensus", "key").unwrap(), None);
+}
+
+#[test]
+fn scan_prefix() {
+    let conn = open();
+
+    storage::set(&conn, "consensus", "dr:2", b"b").unwrap();
+    storage::set(&conn, "consensus", "block:1", b"c").unwrap();
+    storage::set(&conn, "consensus", "dr:1", b"a").unwrap();
+
+    assert_eq!(
+        storage::scan(&conn, "consensus", "dr:").unwrap(),
+        vec![
+            DatabaseEntry {
+                key:   "dr:1".to_string(),
+                value: b"a".to_vec(),
+            },
+            DatabaseEntry {
+                key:   "dr:2".to_string(),
+                value: b"b".to_vec(),
+            },
+        ]
+    );
+    assert_eq!(storage::scan(&conn, "consensus", "").unwrap().len(), 3);
+}
+
+#[test]
+fn namespaces_are_isolated() {
+    let conn = open();
+
+    storage::set(&conn, "consensus", "key", b"consensus").unwrap();
+    storage::set(&conn, "price-feed", "key", b"price-feed").unwrap();
+    storage::delete(&conn, "price-feed", "key").unwrap();
+
+    assert_eq!(
+        storage::get(&conn, "consensus", "key").unwrap(),
+        Some(b"consensus".to_vec())
+    );
+    assert_eq!(storage::get(&conn, "price-feed", "key").unwrap(), None);
+    assert!(storage::scan(&conn, "price-feed", "").unwrap().is_empty());
+}
+
+#[test]
+fn commit_writes() {
+    let mut conn = open();
+    storage::set(&conn, "consensus", "deleted", b"value").unwrap();
+
+    storage::commit(
+        &mut conn,
+        &[
+            DatabaseWrite {
+                namespace: "consensus".to_string(),
+                key:       "key".to_string(),
+                value:     Some(b"value".to_vec()),
+            },
+            DatabaseWrite {
+                namespace: "consensus".to_string(),
+                key:       "deleted".to_string(),
+                value:     None,
+            },
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        storage::get(&conn, "consensus", "key").unwrap(),
+        Some(b"value".to_vec())
+    );
+    assert_eq!(storage::get(&conn, "consensus", "deleted").unwrap(), None);
+}
+
+#[test]
+fn init_migrates_legacy_table() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE data (key TEXT, value TEXT NOT NULL)", params![])
+        .unwrap();
+    conn.execute(
+        "INSERT INTO data (key, value) VALUES ('key', 'old'), ('key', 'new')",
+        params![],
+    )
+    .unwrap();
+
+    storage::init(&mut conn).unwrap();
+
+    assert_eq!(storage::get(&conn, "consensus", "key").unwrap(), Some(b"new".to_vec()));
+    // Running it again doesn't fail now that the old table is gone
+    storage::init(&mut conn).unwrap();
+}
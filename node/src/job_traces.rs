@@ -0,0 +1,43 @@
+use std::collections::{HashMap, VecDeque};
+
+use seda_runtime::ExecutionTrace;
+use seda_runtime_sdk::events::EventId;
+
+/// The traces of the most recent jobs by event, the trace of the event that
+/// was traced the longest ago is dropped once there are more than `capacity`.
+pub struct JobTraces {
+    capacity: usize,
+    traces:   HashMap<EventId, ExecutionTrace>,
+    /// The traced events from the oldest to the most recent
+    order:    VecDeque<EventId>,
+}
+
+impl JobTraces {
+    /// The number of traces the node keeps.
+    pub const CAPACITY: usize = 100;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            traces: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn insert(&mut self, event_id: EventId, trace: ExecutionTrace) {
+        if self.traces.insert(event_id.clone(), trace).is_some() {
+            self.order.retain(|id| id != &event_id);
+        }
+        self.order.push_back(event_id);
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.traces.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, event_id: &str) -> Option<&ExecutionTrace> {
+        self.traces.get(event_id)
+    }
+}
@@ -0,0 +1,30 @@
+use seda_runtime::ExecutionTrace;
+
+use crate::job_traces::JobTraces;
+
+#[test]
+fn oldest_trace_is_dropped() {
+    let mut traces = JobTraces::new(2);
+
+    traces.insert("first".to_string(), ExecutionTrace::default());
+    traces.insert("second".to_string(), ExecutionTrace::default());
+    traces.insert("third".to_string(), ExecutionTrace::default());
+
+    assert!(traces.get("first").is_none());
+    assert!(traces.get("second").is_some());
+    assert!(traces.get("third").is_some());
+}
+
+#[test]
+fn retraced_event_is_kept() {
+    let mut traces = JobTraces::new(2);
+
+    traces.insert("first".to_string(), ExecutionTrace::default());
+    traces.insert("second".to_string(), ExecutionTrace::default());
+    traces.insert("first".to_string(), ExecutionTrace::default());
+    traces.insert("third".to_string(), ExecutionTrace::default());
+
+    assert!(traces.get("first").is_some());
+    assert!(traces.get("second").is_none());
+    assert!(traces.get("third").is_some());
+}
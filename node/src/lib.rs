@@ -7,12 +7,14 @@ mod errors;
 pub use errors::*;
 mod event_queue;
 mod event_queue_handler;
+mod job_traces;
 mod rpc;
 mod runtime_job;
+mod wasm_registry;
 
 mod host;
 
-use actix::prelude::*;
+use actix::{prelude::*, registry::SystemRegistry};
 pub(crate) use host::*;
 pub use host::{ChainCall, ChainView};
 use parking_lot::RwLock;
@@ -30,18 +32,29 @@ use generate_sk::generate_secret_key;
 #[path = ""]
 pub mod test {
     mod event_queue_test;
+    mod generate_sk_test;
+    mod job_traces_test;
+    mod wasm_registry_test;
 }
 pub fn run(seda_server_address: &str, config: NodeConfig, p2p_config: P2PConfig, chain_configs: ChainConfigs) {
     let system = System::new();
     // Initialize actors inside system context
     system.block_on(async {
-        generate_secret_key(config.clone());
+        let config = generate_secret_key(config).expect("Couldn't load the node secret key");
         let (p2p_message_sender, p2p_message_receiver) = channel::<P2PMessage>(100);
         let (p2p_command_sender, p2p_command_receiver) = channel::<P2PCommand>(100);
 
         let known_peers = PeerList::from_vec(&p2p_config.p2p_known_peers);
         let discovery_status = Arc::new(RwLock::new(DiscoveryStatusInner::new(p2p_config.clone(), known_peers)));
 
+        // Registered before the app starts, so every actor gets the host with the
+        // configured database
+        let host = Host::<RuntimeAdapter>::new(&config.db_path)
+            .await
+            .expect("Couldn't open the node database")
+            .start();
+        SystemRegistry::set(host);
+
         // TODO: add number of workers as config with default value
         let app = App::<RuntimeAdapter>::new(
             config.clone(),
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use actix::prelude::*;
 use jsonrpsee::{
@@ -6,25 +6,29 @@ use jsonrpsee::{
     proc_macros::rpc,
     server::{ServerBuilder, ServerHandle},
 };
+use parking_lot::RwLock;
 use seda_p2p::{
     libp2p::{Multiaddr, PeerId},
     DiscoveryStatus,
 };
-use seda_runtime::HostAdapter;
+use seda_runtime::{CancellationToken, ExecutionTrace, HostAdapter};
 use seda_runtime_sdk::{
-    events::{Event, EventData},
+    events::{Event, EventData, EventId},
     p2p::{AddPeerCommand, P2PCommand, RemovePeerCommand},
 };
 use serde_json::Value;
 use tokio::sync::mpsc::Sender;
-use tracing::debug;
+use tracing::{debug, info};
 
-use crate::runtime_job::{RuntimeJob, RuntimeWorker};
+use crate::{
+    job_traces::JobTraces,
+    runtime_job::{RuntimeJob, RuntimeWorker},
+};
 
 #[rpc(server)]
 pub trait Rpc {
     #[method(name = "cli")]
-    async fn cli(&self, args: Vec<String>) -> Result<Vec<String>, Error>;
+    async fn cli(&self, args: Vec<String>, timestamp: u64) -> Result<Vec<String>, Error>;
 
     #[method(name = "add_peer")]
     async fn add_peer(&self, multi_addr: String) -> Result<(), Error>;
@@ -37,26 +41,36 @@ pub trait Rpc {
 
     #[method(name = "discover_peers")]
     async fn discover_peers(&self) -> Result<(), Error>;
+
+    #[method(name = "get_job_trace")]
+    async fn get_job_trace(&self, job_id: String) -> Result<Option<ExecutionTrace>, Error>;
+
+    #[method(name = "cancel_job")]
+    async fn cancel_job(&self, job_id: String) -> Result<bool, Error>;
 }
 
 pub struct CliServer<HA: HostAdapter> {
     runtime_worker:             Addr<RuntimeWorker<HA>>,
     p2p_command_sender_channel: Sender<P2PCommand>,
     discovery_status:           DiscoveryStatus,
+    running_jobs:               Arc<RwLock<HashMap<EventId, CancellationToken>>>,
+    job_traces:                 Arc<RwLock<JobTraces>>,
 }
 
 #[async_trait]
 impl<HA: HostAdapter> RpcServer for CliServer<HA> {
-    async fn cli(&self, args: Vec<String>) -> Result<Vec<String>, Error> {
+    async fn cli(&self, args: Vec<String>, timestamp: u64) -> Result<Vec<String>, Error> {
         debug!("{:?}", &args);
 
         let result = self
             .runtime_worker
             .send(RuntimeJob {
-                event: Event {
-                    id:   "test".to_string(),
+                event:              Event {
+                    id: "test".to_string(),
                     data: EventData::CliCall(args),
+                    timestamp,
                 },
+                cancellation_token: CancellationToken::default(),
             })
             .await
             .map_err(|err| Error::Custom(err.to_string()))?;
@@ -106,6 +120,24 @@ impl<HA: HostAdapter> RpcServer for CliServer<HA> {
 
         Ok(())
     }
+
+    async fn get_job_trace(&self, job_id: String) -> Result<Option<ExecutionTrace>, Error> {
+        Ok(self.job_traces.read().get(&job_id).cloned())
+    }
+
+    /// Cancels the running job of the given event. Returns `false` when there
+    /// is no running job for that event.
+    async fn cancel_job(&self, job_id: String) -> Result<bool, Error> {
+        match self.running_jobs.read().get(&job_id) {
+            Some(cancellation_token) => {
+                info!("Cancelling job for event `{job_id}`");
+                cancellation_token.cancel();
+
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
 }
 pub struct JsonRpcServer {
     handle: ServerHandle,
@@ -117,12 +149,16 @@ impl JsonRpcServer {
         addrs: &str,
         p2p_command_sender_channel: Sender<P2PCommand>,
         discovery_status: DiscoveryStatus,
+        running_jobs: Arc<RwLock<HashMap<EventId, CancellationToken>>>,
+        job_traces: Arc<RwLock<JobTraces>>,
     ) -> Result<Self, Error> {
         let server = ServerBuilder::default().build(addrs).await?;
         let rpc = CliServer {
             runtime_worker,
             p2p_command_sender_channel,
             discovery_status,
+            running_jobs,
+            job_traces,
         };
         let handle = server.start(rpc.into_rpc())?;
 
@@ -1,15 +1,24 @@
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use actix::{prelude::*, Handler, Message};
 use parking_lot::Mutex;
 use seda_config::{ChainConfigs, NodeConfig};
-use seda_runtime::{HostAdapter, InMemory, Result, RunnableRuntime, Runtime, VmConfig, VmResult};
-use seda_runtime_sdk::{
-    events::{Event, EventData},
-    p2p::P2PCommand,
+use seda_runtime::{
+    CancellationToken,
+    HostAdapter,
+    InMemory,
+    Result,
+    RunnableRuntime,
+    Runtime,
+    RuntimeError,
+    VmConfig,
+    VmResult,
 };
-use tokio::sync::mpsc::Sender;
-use tracing::info;
+use seda_runtime_sdk::events::{Event, EventData};
+use sha2::{Digest, Sha256};
+use tracing::{error, info};
+
+use crate::wasm_registry::WasmRegistry;
 
 #[derive(MessageResponse)]
 pub struct RuntimeJobResult {
@@ -19,39 +28,54 @@ pub struct RuntimeJobResult {
 #[derive(Message)]
 #[rtype(result = "Result<RuntimeJobResult>")]
 pub struct RuntimeJob {
-    pub event: Event,
+    pub event:              Event,
+    pub cancellation_token: CancellationToken,
 }
 
 pub struct RuntimeWorker<HA: HostAdapter> {
-    pub runtime:                    Option<Runtime<HA>>,
-    pub node_config:                NodeConfig,
-    pub chain_configs:              ChainConfigs,
-    pub p2p_command_sender_channel: Sender<P2PCommand>,
+    /// A runtime for every binary in the registry
+    pub runtimes:      HashMap<String, Runtime<HA>>,
+    /// Drives the runtime futures, gives them access to the tokio timers
+    pub executor:      Option<tokio::runtime::Runtime>,
+    /// Shared by all the workers, a binary is only compiled once
+    pub wasm_registry: WasmRegistry,
+    pub node_config:   NodeConfig,
+    pub chain_configs: ChainConfigs,
 }
 
 impl<HA: HostAdapter> Actor for RuntimeWorker<HA> {
     type Context = SyncContext<Self>;
 
     fn started(&mut self, _ctx: &mut Self::Context) {
-        // TODO: Replace the binary conditionally with the consensus binary
-        let mut path_prefix = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        #[cfg(debug_assertions)]
-        path_prefix.push("../target/wasm32-wasi/debug/consensus.wasm");
-        #[cfg(not(debug_assertions))]
-        path_prefix.push("../target/wasm32-wasi/release/consensus.wasm");
-
-        let node_config = self.node_config.clone();
-        let chain_configs = self.chain_configs.clone();
-        // TODO: when conditionally loading the consensus binary see if it allows full
-        // or limited features
-        let mut runtime =
-            futures::executor::block_on(
-                async move { Runtime::new(node_config, chain_configs, false).await.expect("TODO") },
-            );
-
-        runtime.init(fs::read(path_prefix).unwrap()).unwrap();
-
-        self.runtime = Some(runtime);
+        // A binary that can't be set up is left out, its jobs fail with
+        // `WasmBinaryNotFound` instead of taking the worker down
+        for (name, hash) in self.wasm_registry.binaries() {
+            match self.setup_runtime(name, hash) {
+                Ok(runtime) => {
+                    self.runtimes.insert(name.clone(), runtime);
+                }
+                Err(err) => error!("Couldn't set up the WASM binary `{name}`, it won't be available: {err}"),
+            }
+        }
+
+        match tokio::runtime::Builder::new_current_thread().enable_time().build() {
+            Ok(executor) => self.executor = Some(executor),
+            Err(err) => error!("Couldn't build the runtime worker executor, its jobs will fail: {err}"),
+        }
+    }
+}
+
+impl<HA: HostAdapter> RuntimeWorker<HA> {
+    fn setup_runtime(&self, name: &str, hash: &str) -> Result<Runtime<HA>> {
+        let limited = WasmRegistry::is_limited(name);
+        let mut runtime = futures::executor::block_on(Runtime::new(
+            self.node_config.clone(),
+            self.chain_configs.clone(),
+            limited,
+        ))?;
+
+        runtime.init_with_cache(hash, &self.wasm_registry.module_cache)?;
+        Ok(runtime)
     }
 }
 
@@ -61,27 +85,42 @@ impl<HA: HostAdapter> Handler<RuntimeJob> for RuntimeWorker<HA> {
     fn handle(&mut self, msg: RuntimeJob, _ctx: &mut Self::Context) -> Self::Result {
         let memory_adapter = Arc::new(Mutex::new(InMemory::default()));
 
+        let binary_name = WasmRegistry::binary_for_event(&msg.event.data)?.to_string();
+        let runtime = self
+            .runtimes
+            .get(&binary_name)
+            .ok_or_else(|| RuntimeError::WasmBinaryNotFound(binary_name.clone()))?;
+
+        // Every node that runs the event draws the same random bytes, so the
+        // seed only covers what they all received
+        let seed = Sha256::digest(serde_json::to_vec(&(&msg.event.id, &msg.event.data))?).into();
+        let timestamp = msg.event.timestamp;
+
         let args: Vec<String> = match msg.event.data {
             EventData::ChainTick => vec![],
             EventData::CliCall(args) => args,
             // TODO: Make args accept byes only
             EventData::P2PMessage(message) => vec!["p2p".to_string(), String::from_utf8(message.data).unwrap()],
+            EventData::DataRequest(data_request) => data_request.args,
         };
 
         let vm_config = VmConfig {
             args,
-            program_name: "test".to_string(),
-            debug: false,
+            program_name: binary_name,
+            debug: self.node_config.trace_jobs,
             start_func: None,
+            gas_limit: Some(self.node_config.gas_limit),
+            cancellation_token: msg.cancellation_token,
+            timestamp,
+            seed,
         };
 
-        let runtime = self.runtime.as_ref().unwrap();
+        let executor = self
+            .executor
+            .as_ref()
+            .ok_or_else(|| RuntimeError::NodeError("The runtime worker has no executor".to_string()))?;
 
-        let res = futures::executor::block_on(runtime.start_runtime(
-            vm_config,
-            memory_adapter,
-            self.p2p_command_sender_channel.clone(),
-        ));
+        let res = executor.block_on(runtime.start_runtime(vm_config, memory_adapter));
         // TODO maybe set up a prettier log format rather than debug of this type?
 
         info!(vm_result = ?res);
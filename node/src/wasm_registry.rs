@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use seda_config::{NodeConfig, NodeConfigInner};
+use seda_runtime::{ModuleCache, RuntimeError};
+use seda_runtime_sdk::events::EventData;
+use tracing::info;
+
+use crate::{NodeError, Result};
+
+/// The WASM binaries the node can run, by the name they have in the config.
+#[derive(Clone)]
+pub struct WasmRegistry {
+    pub module_cache: ModuleCache,
+    /// The names of the binaries with the hash of their module in the cache
+    binaries:         HashMap<String, String>,
+}
+
+impl WasmRegistry {
+    /// Loads all the configured binaries into the module cache, fails when a
+    /// binary can't be loaded or is missing the exports the VM needs.
+    pub fn load(node_config: &NodeConfig) -> Result<Self> {
+        let module_cache = ModuleCache::new(node_config.max_memory_pages, Some(node_config.wasm_cache_path.clone()));
+
+        let mut binaries = HashMap::new();
+        for (name, source) in &node_config.wasm_binaries {
+            let hash = module_cache.load(source).map_err(|error| NodeError::WasmBinaryError {
+                name: name.clone(),
+                error,
+            })?;
+            info!("Loaded WASM binary `{name}` ({hash})");
+            binaries.insert(name.clone(), hash);
+        }
+
+        if !binaries.contains_key(NodeConfigInner::CONSENSUS_BINARY) {
+            return Err(NodeError::MissingWasmBinary(
+                NodeConfigInner::CONSENSUS_BINARY.to_string(),
+            ));
+        }
+
+        Ok(Self { module_cache, binaries })
+    }
+
+    /// Iterates over the names of the binaries with the hash of their module.
+    pub fn binaries(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.binaries.iter()
+    }
+
+    /// The name of the binary that handles the event. A data request always
+    /// runs in limited mode, so it can't name the consensus binary.
+    pub fn binary_for_event(event: &EventData) -> seda_runtime::Result<&str> {
+        match event {
+            EventData::ChainTick | EventData::P2PMessage(_) | EventData::CliCall(_) => {
+                Ok(NodeConfigInner::CONSENSUS_BINARY)
+            }
+            EventData::DataRequest(data_request) if !Self::is_limited(&data_request.program) => {
+                Err(RuntimeError::TrustedBinaryNotAllowed(data_request.program.clone()))
+            }
+            EventData::DataRequest(data_request) => Ok(&data_request.program),
+        }
+    }
+
+    /// Only the consensus binary is trusted with the full set of actions,
+    /// every other binary runs in limited mode.
+    pub fn is_limited(name: &str) -> bool {
+        name != NodeConfigInner::CONSENSUS_BINARY
+    }
+}
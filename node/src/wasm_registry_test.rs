@@ -0,0 +1,43 @@
+use seda_config::NodeConfigInner;
+use seda_runtime::RuntimeError;
+use seda_runtime_sdk::events::{DataRequestEvent, EventData};
+
+use crate::wasm_registry::WasmRegistry;
+
+#[test]
+fn consensus_binary_handles_node_events() {
+    assert_eq!(
+        WasmRegistry::binary_for_event(&EventData::ChainTick).unwrap(),
+        NodeConfigInner::CONSENSUS_BINARY
+    );
+    assert_eq!(
+        WasmRegistry::binary_for_event(&EventData::CliCall(vec!["hello".to_string()])).unwrap(),
+        NodeConfigInner::CONSENSUS_BINARY
+    );
+    assert!(!WasmRegistry::is_limited(NodeConfigInner::CONSENSUS_BINARY));
+}
+
+#[test]
+fn data_request_runs_its_program_limited() {
+    let event = EventData::DataRequest(DataRequestEvent {
+        program: "price-feed".to_string(),
+        args:    vec!["btc".to_string()],
+    });
+
+    let program = WasmRegistry::binary_for_event(&event).unwrap();
+    assert_eq!(program, "price-feed");
+    assert!(WasmRegistry::is_limited(program));
+}
+
+#[test]
+fn data_request_cannot_run_consensus_binary() {
+    let event = EventData::DataRequest(DataRequestEvent {
+        program: NodeConfigInner::CONSENSUS_BINARY.to_string(),
+        args:    vec![],
+    });
+
+    assert!(matches!(
+        WasmRegistry::binary_for_event(&event),
+        Err(RuntimeError::TrustedBinaryNotAllowed(program)) if program == NodeConfigInner::CONSENSUS_BINARY
+    ));
+}
@@ -1,5 +1,7 @@
 use wasmer_wasi::{WasiEnv, WasiState};
 
+use crate::CancellationToken;
+
 #[derive(Debug, Clone)]
 pub struct VmConfig {
     /// Name of the binary, ex. "consensus", "fisherman", etc
@@ -11,7 +13,25 @@ pub struct VmConfig {
     /// Arguments to pass to the WASM binary
     pub args: Vec<String>,
 
+    /// Returns the trace of the whole promise queue in the
+    /// [VmResult](crate::VmResult), see [ExecutionTrace](crate::ExecutionTrace)
     pub debug: bool,
+
+    /// The maximum amount of gas the execution may consume, `None` means
+    /// unlimited
+    pub gas_limit: Option<u64>,
+
+    /// Allows the execution to be cancelled while it's running
+    pub cancellation_token: CancellationToken,
+
+    /// The consensus time the binary sees in ms since the unix epoch, the
+    /// timestamp of the event it runs, see
+    /// [ConsensusContext](crate::ConsensusContext)
+    pub timestamp: u64,
+
+    /// Seeds the deterministic RNG of the execution, derived from the event so
+    /// every node that runs it draws the same bytes
+    pub seed: [u8; 32],
 }
 
 impl VmConfig {
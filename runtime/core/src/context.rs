@@ -1,10 +1,81 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use parking_lot::Mutex;
+use seda_config::NodeConfig;
+use seda_runtime_sdk::GuestConfig;
 use wasmer::{HostEnvInitError, Instance, LazyInit, Memory, WasmerEnv};
 
 use super::PromiseQueue;
-use crate::InMemory;
+use crate::{CancellationToken, ConsensusContext, DatabaseWriteBuffer, HostAdapter, InMemory, Result, RuntimeError};
+
+/// How often a synchronous import that waits for the host checks whether the
+/// job timed out or got cancelled.
+const INTERRUPT_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The part of the [HostAdapter] the synchronous imports use, they block the
+/// WASM instance until the host answered, see [HostContext::db_get].
+pub trait SyncHostAdapter: Send + Sync {
+    fn db_get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>, String>;
+}
+
+impl<HA: HostAdapter> SyncHostAdapter for HA {
+    fn db_get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>, String> {
+        futures::executor::block_on(HostAdapter::db_get(self, namespace, key)).map_err(|err| err.to_string())
+    }
+}
+
+/// What the synchronous imports need to know about the execution they are
+/// part of.
+#[derive(Clone)]
+pub struct HostContext {
+    pub host_adapter:       Arc<dyn SyncHostAdapter>,
+    pub node_config:        NodeConfig,
+    pub guest_config:       GuestConfig,
+    pub program_name:       String,
+    pub limited:            bool,
+    /// The database writes of the whole promise queue, the synchronous writes
+    /// are committed with the others
+    pub db_writes:          Arc<Mutex<DatabaseWriteBuffer>>,
+    pub consensus:          ConsensusContext,
+    /// The running call is interrupted at its next import once the job is past
+    /// its deadline or cancelled, see [VmContext::check_interrupted]
+    pub deadline:           Instant,
+    pub cancellation_token: CancellationToken,
+}
+
+impl HostContext {
+    /// Reads from the database of the host for a synchronous import. The
+    /// adapter runs on its own thread, so the wait stops once the job timed
+    /// out or got cancelled even when the adapter never answers.
+    pub fn db_get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let host_adapter = self.host_adapter.clone();
+        let (namespace, key) = (namespace.to_string(), key.to_string());
+        let (result_sender, result_receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // The import stopped waiting when the receiver is gone
+            result_sender.send(host_adapter.db_get(&namespace, &key)).ok();
+        });
+
+        loop {
+            match result_receiver.recv_timeout(INTERRUPT_CHECK_INTERVAL) {
+                Ok(result) => return Ok(result?),
+                Err(RecvTimeoutError::Timeout) => {
+                    if self.cancellation_token.is_cancelled() || Instant::now() >= self.deadline {
+                        return Err(RuntimeError::Interrupted);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err("The database read failed".into()),
+            }
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct VmContext {
@@ -13,6 +84,12 @@ pub struct VmContext {
     pub memory_adapter:        Arc<Mutex<InMemory>>,
     pub promise_queue:         Arc<Mutex<PromiseQueue>>,
     pub current_promise_queue: Arc<Mutex<PromiseQueue>>,
+    /// The typed input of the `CallSelf` action that runs
+    pub call_input:            Vec<u8>,
+    pub host:                  HostContext,
+    /// The value of the last synchronous import that returned data, WASM
+    /// reads it with `host_result_write` once it knows the length
+    pub host_result:           Arc<Mutex<Vec<u8>>>,
 }
 
 impl WasmerEnv for VmContext {
@@ -25,10 +102,23 @@ impl WasmerEnv for VmContext {
 }
 
 impl VmContext {
+    /// Traps the running call once the job timed out or got cancelled. Every
+    /// import checks this first, a call that never calls the host only stops
+    /// when it runs out of gas.
+    pub fn check_interrupted(&self) -> Result<()> {
+        if self.host.cancellation_token.is_cancelled() || Instant::now() >= self.host.deadline {
+            return Err(RuntimeError::Interrupted);
+        }
+
+        Ok(())
+    }
+
     pub fn create_vm_context(
         memory_adapter: Arc<Mutex<InMemory>>,
         current_promise_queue: Arc<Mutex<PromiseQueue>>,
         promise_queue: Arc<Mutex<PromiseQueue>>,
+        call_input: Vec<u8>,
+        host: HostContext,
     ) -> VmContext {
         VmContext {
             result: Arc::new(Mutex::new(Vec::new())),
@@ -36,6 +126,9 @@ impl VmContext {
             memory: LazyInit::new(),
             current_promise_queue,
             promise_queue,
+            call_input,
+            host,
+            host_result: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+
+/// A random number generator every node seeds the same way for an event, so
+/// they all draw the same bytes. Hashes the seed with a counter, one SHA-256
+/// block at a time.
+#[derive(Clone, Debug)]
+pub struct DeterministicRng {
+    seed:    [u8; 32],
+    counter: u64,
+}
+
+impl DeterministicRng {
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    pub fn fill(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(32) {
+            let block = Sha256::new()
+                .chain_update(self.seed)
+                .chain_update(self.counter.to_le_bytes())
+                .finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            self.counter += 1;
+        }
+    }
+}
+
+/// The time and randomness a WASM binary sees, the same on every node that
+/// runs the event unlike the clock and entropy of the host.
+#[derive(Clone, Debug)]
+pub struct ConsensusContext {
+    /// The consensus time of the execution in ms since the unix epoch, the
+    /// timestamp of the event it runs
+    pub timestamp: u64,
+    /// Shared by all the calls of the execution, so they draw different bytes
+    pub rng:       Arc<Mutex<DeterministicRng>>,
+}
+
+impl ConsensusContext {
+    pub fn new(timestamp: u64, seed: [u8; 32]) -> Self {
+        Self {
+            timestamp,
+            rng: Arc::new(Mutex::new(DeterministicRng::new(seed))),
+        }
+    }
+}
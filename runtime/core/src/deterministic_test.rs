@@ -0,0 +1,39 @@
+use crate::DeterministicRng;
+
+#[test]
+fn same_seed_draws_same_bytes() {
+    let mut first = DeterministicRng::new([7; 32]);
+    let mut second = DeterministicRng::new([7; 32]);
+
+    let mut first_bytes = [0; 48];
+    let mut second_bytes = [0; 48];
+    first.fill(&mut first_bytes);
+    second.fill(&mut second_bytes);
+
+    assert_eq!(first_bytes, second_bytes);
+}
+
+#[test]
+fn draws_continue_the_stream() {
+    let mut rng = DeterministicRng::new([7; 32]);
+
+    let mut first_bytes = [0; 32];
+    let mut second_bytes = [0; 32];
+    rng.fill(&mut first_bytes);
+    rng.fill(&mut second_bytes);
+
+    assert_ne!(first_bytes, second_bytes);
+}
+
+#[test]
+fn different_seeds_draw_different_bytes() {
+    let mut first = DeterministicRng::new([1; 32]);
+    let mut second = DeterministicRng::new([2; 32]);
+
+    let mut first_bytes = [0; 32];
+    let mut second_bytes = [0; 32];
+    first.fill(&mut first_bytes);
+    second.fill(&mut second_bytes);
+
+    assert_ne!(first_bytes, second_bytes);
+}
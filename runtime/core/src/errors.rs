@@ -1,8 +1,7 @@
 use std::num::ParseIntError;
 
-use seda_runtime_sdk::{p2p::P2PCommand, SDKError};
+use seda_runtime_sdk::SDKError;
 use thiserror::Error;
-use tokio::sync::mpsc::error::SendError;
 use wasmer::{CompileError, ExportError, InstantiationError};
 use wasmer_wasi::{FsError, WasiError, WasiStateCreationError};
 
@@ -22,6 +21,21 @@ pub enum RuntimeError {
     #[error(transparent)]
     FunctionNotFound(#[from] ExportError),
 
+    #[error("Invalid WASM binary: {0}")]
+    InvalidWasmBinary(String),
+
+    #[error("No WASM binary named `{0}`")]
+    WasmBinaryNotFound(String),
+
+    #[error("A data request can't run the trusted `{0}` binary")]
+    TrustedBinaryNotAllowed(String),
+
+    #[error("The execution was interrupted, the job timed out or got cancelled")]
+    Interrupted,
+
+    #[error("WASM module `{0}` is not in the cache")]
+    ModuleNotCached(String),
+
     #[error("Error while running: {0}")]
     ExecutionError(#[from] wasmer::RuntimeError),
 
@@ -48,9 +62,6 @@ pub enum RuntimeError {
     #[error("Chain Adapter Error: {0}")]
     ChainAdapterError(#[from] seda_chains::ChainAdapterError),
 
-    #[error("P2P Command Channel Error: {0}")]
-    P2PCommandChannelError(#[from] SendError<P2PCommand>),
-
     #[error("BN254 Error: {0}")]
     Bn254Error(#[from] bn254::Error),
 
@@ -1,9 +1,146 @@
 use std::fmt::Display;
 
-use seda_chains::Client;
 use seda_config::{ChainConfigs, NodeConfig};
-use seda_runtime_sdk::{events::Event, Chain};
+pub use seda_runtime_macros::Adapter;
+use seda_runtime_sdk::{events::Event, Chain, DatabaseEntry, HttpAction, HttpResponse};
+
+use crate::DatabaseWrite;
+
+/// The node storage the promises of a WASM binary read and write.
+#[async_trait::async_trait]
+pub trait DatabaseAdapter: Send + Sync + Unpin + 'static {
+    type Error: Display + std::error::Error;
+
+    async fn new(config: &ChainConfigs) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    async fn get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>, Self::Error>;
+    async fn set(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), Self::Error>;
+    async fn delete(&self, namespace: &str, key: &str) -> Result<(), Self::Error>;
+    async fn scan(&self, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>, Self::Error>;
+    /// Applies all the writes of an execution in one transaction.
+    async fn commit(&self, writes: Vec<DatabaseWrite>) -> Result<(), Self::Error>;
+}
+
+#[async_trait::async_trait]
+pub trait HttpAdapter: Send + Sync + Unpin + 'static {
+    type Error: Display + std::error::Error;
+
+    async fn new(config: &ChainConfigs) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    async fn fetch(&self, action: HttpAction) -> Result<HttpResponse, Self::Error>;
+}
+
+/// Sends transactions to and reads the state of the chains the node is
+/// connected to.
+#[async_trait::async_trait]
+pub trait ChainInteractorAdapter: Send + Sync + Unpin + 'static {
+    type Error: Display + std::error::Error;
+
+    async fn new(config: &ChainConfigs) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    async fn call(
+        &self,
+        chain: Chain,
+        contract_id: &str,
+        method_name: &str,
+        args: Vec<u8>,
+        deposit: u128,
+        node_config: NodeConfig,
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    async fn view(
+        &self,
+        chain: Chain,
+        contract_id: &str,
+        method_name: &str,
+        args: Vec<u8>,
+    ) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Receives the events a WASM binary triggers, e.g. to queue them for the
+/// node.
+#[async_trait::async_trait]
+pub trait EventSinkAdapter: Send + Sync + Unpin + 'static {
+    type Error: Display + std::error::Error;
+
+    async fn new(config: &ChainConfigs) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    async fn trigger(&self, event: Event) -> Result<(), Self::Error>;
+}
 
+#[async_trait::async_trait]
+pub trait P2PAdapter: Send + Sync + Unpin + 'static {
+    type Error: Display + std::error::Error;
+
+    async fn new(config: &ChainConfigs) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    /// Sends the data to all the peers of the node.
+    async fn broadcast(&self, data: Vec<u8>) -> Result<(), Self::Error>;
+}
+
+/// The backends a [HostAdapter] is built from, one per capability. Usually
+/// implemented with `#[derive(Adapter)]` on a struct that holds one field per
+/// backend:
+///
+/// ```ignore
+/// #[derive(Adapter)]
+/// #[adapter(
+///     error = NodeError,
+///     database = HostDatabase,
+///     http = HostHttp,
+///     chain_interactor = HostChainInteractor,
+///     event_sink = HostEventSink,
+///     p2p = HostP2P
+/// )]
+/// pub struct RuntimeAdapter {
+///     database:         HostDatabase,
+///     http:             HostHttp,
+///     chain_interactor: HostChainInteractor,
+///     event_sink:       HostEventSink,
+///     p2p:              HostP2P,
+/// }
+/// ```
+pub trait HostAdapterTypes: Send + Sync + Unpin + 'static {
+    type Error: Display
+        + std::error::Error
+        + From<<Self::Database as DatabaseAdapter>::Error>
+        + From<<Self::Http as HttpAdapter>::Error>
+        + From<<Self::ChainInteractor as ChainInteractorAdapter>::Error>
+        + From<<Self::EventSink as EventSinkAdapter>::Error>
+        + From<<Self::P2P as P2PAdapter>::Error>;
+    type Database: DatabaseAdapter;
+    type Http: HttpAdapter;
+    type ChainInteractor: ChainInteractorAdapter;
+    type EventSink: EventSinkAdapter;
+    type P2P: P2PAdapter;
+
+    fn from_parts(
+        database: Self::Database,
+        http: Self::Http,
+        chain_interactor: Self::ChainInteractor,
+        event_sink: Self::EventSink,
+        p2p: Self::P2P,
+    ) -> Self;
+
+    fn database(&self) -> &Self::Database;
+    fn http(&self) -> &Self::Http;
+    fn chain_interactor(&self) -> &Self::ChainInteractor;
+    fn event_sink(&self) -> &Self::EventSink;
+    fn p2p(&self) -> &Self::P2P;
+}
+
+/// The host functionality the runtime uses, implemented for every
+/// [HostAdapterTypes] by delegating to its backends.
 #[async_trait::async_trait]
 pub trait HostAdapter: Send + Sync + Unpin + 'static {
     type Error: Display + std::error::Error;
@@ -12,11 +149,13 @@ pub trait HostAdapter: Send + Sync + Unpin + 'static {
     where
         Self: Sized;
 
-    fn select_client_from_chain(&self, chain: Chain) -> Client;
-
-    async fn db_get(&self, key: &str) -> Result<Option<String>, Self::Error>;
-    async fn db_set(&self, key: &str, value: &str) -> Result<(), Self::Error>;
-    async fn http_fetch(&self, url: &str) -> Result<String, Self::Error>;
+    async fn db_get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>, Self::Error>;
+    async fn db_set(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), Self::Error>;
+    async fn db_delete(&self, namespace: &str, key: &str) -> Result<(), Self::Error>;
+    async fn db_scan(&self, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>, Self::Error>;
+    /// Applies all the writes of an execution in one transaction.
+    async fn db_commit(&self, writes: Vec<DatabaseWrite>) -> Result<(), Self::Error>;
+    async fn http_fetch(&self, action: HttpAction) -> Result<HttpResponse, Self::Error>;
 
     async fn chain_call(
         &self,
@@ -37,4 +176,80 @@ pub trait HostAdapter: Send + Sync + Unpin + 'static {
     ) -> Result<Vec<u8>, Self::Error>;
 
     async fn trigger_event(&self, event: Event) -> Result<(), Self::Error>;
+    async fn p2p_broadcast(&self, data: Vec<u8>) -> Result<(), Self::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T: HostAdapterTypes> HostAdapter for T {
+    type Error = T::Error;
+
+    async fn new(config: ChainConfigs) -> Result<Self, Self::Error> {
+        let database = T::Database::new(&config).await?;
+        let http = T::Http::new(&config).await?;
+        let chain_interactor = T::ChainInteractor::new(&config).await?;
+        let event_sink = T::EventSink::new(&config).await?;
+        let p2p = T::P2P::new(&config).await?;
+
+        Ok(T::from_parts(database, http, chain_interactor, event_sink, p2p))
+    }
+
+    async fn db_get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.database().get(namespace, key).await?)
+    }
+
+    async fn db_set(&self, namespace: &str, key: &str, value: &[u8]) -> Result<(), Self::Error> {
+        Ok(self.database().set(namespace, key, value).await?)
+    }
+
+    async fn db_delete(&self, namespace: &str, key: &str) -> Result<(), Self::Error> {
+        Ok(self.database().delete(namespace, key).await?)
+    }
+
+    async fn db_scan(&self, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>, Self::Error> {
+        Ok(self.database().scan(namespace, prefix).await?)
+    }
+
+    async fn db_commit(&self, writes: Vec<DatabaseWrite>) -> Result<(), Self::Error> {
+        Ok(self.database().commit(writes).await?)
+    }
+
+    async fn http_fetch(&self, action: HttpAction) -> Result<HttpResponse, Self::Error> {
+        Ok(self.http().fetch(action).await?)
+    }
+
+    async fn chain_call(
+        &self,
+        chain: Chain,
+        contract_id: &str,
+        method_name: &str,
+        args: Vec<u8>,
+        deposit: u128,
+        node_config: NodeConfig,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Ok(self
+            .chain_interactor()
+            .call(chain, contract_id, method_name, args, deposit, node_config)
+            .await?)
+    }
+
+    async fn chain_view(
+        &self,
+        chain: Chain,
+        contract_id: &str,
+        method_name: &str,
+        args: Vec<u8>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Ok(self
+            .chain_interactor()
+            .view(chain, contract_id, method_name, args)
+            .await?)
+    }
+
+    async fn trigger_event(&self, event: Event) -> Result<(), Self::Error> {
+        Ok(self.event_sink().trigger(event).await?)
+    }
+
+    async fn p2p_broadcast(&self, data: Vec<u8>) -> Result<(), Self::Error> {
+        Ok(self.p2p().broadcast(data).await?)
+    }
 }
@@ -1,9 +1,22 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use borsh::BorshSerialize;
+use ed25519_dalek::Verifier;
+use rand::RngCore;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId, Signature},
+    Message,
+    PublicKey,
+    Secp256k1,
+};
 use seda_runtime_sdk::Level;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use wasmer::{imports, Array, Function, ImportObject, Memory, Module, Store, WasmPtr};
-use wasmer_wasi::WasiEnv;
+use wasmer_wasi::{get_wasi_version, types::__WASI_EOVERFLOW, WasiEnv};
 
 use super::{Result, RuntimeError, VmContext};
-use crate::MemoryAdapter;
+use crate::{runtime::db_namespace, MemoryAdapter};
 
 /// Wrapper around memory.get_ref to implement the RuntimeError
 fn get_memory(env: &VmContext) -> Result<&Memory> {
@@ -13,6 +26,8 @@ fn get_memory(env: &VmContext) -> Result<&Memory> {
 /// Adds a new promise to the promises stack
 pub fn promise_then_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn promise_result_write(env: &VmContext, ptr: WasmPtr<u8, Array>, length: i32) -> Result<()> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
         let mut promises_queue_ref = env.promise_queue.lock();
 
@@ -30,26 +45,80 @@ pub fn promise_then_import_obj(store: &Store, vm_context: VmContext) -> Function
     Function::new_native_with_env(store, vm_context, promise_result_write)
 }
 
-/// Gets the length (stringified) of the promise status
+/// Encodes the status of the promise at the given index of the current queue.
+/// Version 1 of the ABI uses borsh, binaries built before the ABI was
+/// versioned still get JSON.
+fn encode_promise_status(env: &VmContext, promise_index: i32, abi_v1: bool) -> Result<Vec<u8>> {
+    let promises_queue_ref = env.current_promise_queue.lock();
+
+    let promise_info = promises_queue_ref
+        .queue
+        .get(promise_index as usize)
+        .ok_or_else(|| RuntimeError::VmHostError(format!("Could not find promise at index: {promise_index}")))?;
+
+    if abi_v1 {
+        Ok(promise_info.status.try_to_vec()?)
+    } else {
+        Ok(serde_json::to_vec(&promise_info.status)?)
+    }
+}
+
+/// Writes the encoded status of the promise to the WASM memory
+fn write_promise_status(
+    env: &VmContext,
+    promise_index: i32,
+    result_data_ptr: WasmPtr<u8, Array>,
+    result_data_length: i64,
+    abi_v1: bool,
+) -> Result<()> {
+    let memory_ref = get_memory(env)?;
+    let promise_status_bytes = encode_promise_status(env, promise_index, abi_v1)?;
+
+    write_bytes(memory_ref, result_data_ptr, result_data_length, &promise_status_bytes)
+}
+
+/// Gets the length of the borsh encoded promise status
+pub fn promise_status_length_v1_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn promise_status_length_v1(env: &VmContext, promise_index: i32) -> Result<i64> {
+        env.check_interrupted()?;
+
+        Ok(encode_promise_status(env, promise_index, true)?.len() as i64)
+    }
+
+    Function::new_native_with_env(store, vm_context, promise_status_length_v1)
+}
+
+/// Writes the borsh encoded status of the promise to the WASM memory
+pub fn promise_status_write_v1_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn promise_status_write_v1(
+        env: &VmContext,
+        promise_index: i32,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        write_promise_status(env, promise_index, result_data_ptr, result_data_length, true)
+    }
+
+    Function::new_native_with_env(store, vm_context, promise_status_write_v1)
+}
+
+/// Gets the length (stringified) of the promise status, compatibility shim for
+/// binaries built before version 1 of the ABI
 pub fn promise_status_length_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn promise_status_length(env: &VmContext, promise_index: i32) -> Result<i64> {
-        let promises_queue_ref = env.current_promise_queue.lock();
-
-        let promise_info = promises_queue_ref
-            .queue
-            .get(promise_index as usize)
-            .ok_or_else(|| format!("Could not find promise at index: {promise_index}"))?;
+        env.check_interrupted()?;
 
         // The length depends on the full status enum + result in JSON
-        let status = serde_json::to_string(&promise_info.status)?;
-
-        Ok(status.len() as i64)
+        Ok(encode_promise_status(env, promise_index, false)?.len() as i64)
     }
 
     Function::new_native_with_env(store, vm_context, promise_status_length)
 }
 
-/// Writes the status of the promise to the WASM memory
+/// Writes the JSON status of the promise to the WASM memory, compatibility
+/// shim for binaries built before version 1 of the ABI
 pub fn promise_status_write_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn promise_status_write(
         env: &VmContext,
@@ -57,28 +126,9 @@ pub fn promise_status_write_import_obj(store: &Store, vm_context: VmContext) ->
         result_data_ptr: WasmPtr<u8, Array>,
         result_data_length: i64,
     ) -> Result<()> {
-        let memory_ref = get_memory(env)?;
-        let promises_ref = env.current_promise_queue.lock();
-        let promise_info = promises_ref
-            .queue
-            .get(promise_index as usize)
-            .ok_or_else(|| RuntimeError::VmHostError(format!("Could not find promise at index: {promise_index}")))?;
-
-        let promise_status = serde_json::to_string(&promise_info.status)?;
-
-        let promise_status_bytes = promise_status.as_bytes();
-        let derefed_ptr = result_data_ptr
-            .deref(memory_ref, 0, result_data_length as u32)
-            .ok_or("Invalid pointer")?;
+        env.check_interrupted()?;
 
-        for index in 0..result_data_length {
-            derefed_ptr
-                .get(index as usize)
-                .ok_or("Writing out of bounds to memory")?
-                .set(promise_status_bytes[index as usize]);
-        }
-
-        Ok(())
+        write_promise_status(env, promise_index, result_data_ptr, result_data_length, false)
     }
 
     Function::new_native_with_env(store, vm_context, promise_status_write)
@@ -93,6 +143,8 @@ pub fn memory_read_import_obj(store: &Store, vm_context: VmContext) -> Function
         result_data_ptr: WasmPtr<u8, Array>,
         result_data_length: i64,
     ) -> Result<()> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
         let key = key
             .get_utf8_string(memory_ref, key_length as u32)
@@ -127,6 +179,8 @@ pub fn memory_read_import_obj(store: &Store, vm_context: VmContext) -> Function
 /// WASM.
 pub fn memory_read_length_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn memory_read_length(env: &VmContext, key: WasmPtr<u8, Array>, key_length: i64) -> Result<i64> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
         let key = key
             .get_utf8_string(memory_ref, key_length as u32)
@@ -150,6 +204,8 @@ pub fn memory_write_import_obj(store: &Store, vm_context: VmContext) -> Function
         value: WasmPtr<u8, Array>,
         value_len: i64,
     ) -> Result<()> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
         let key = key
             .get_utf8_string(memory_ref, key_length as u32)
@@ -168,6 +224,8 @@ pub fn memory_write_import_obj(store: &Store, vm_context: VmContext) -> Function
 
 fn execution_result_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn execution_result(env: &VmContext, result_ptr: WasmPtr<u8, Array>, result_length: i32) -> Result<()> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
 
         let result = result_ptr
@@ -185,6 +243,328 @@ fn execution_result_import_obj(store: &Store, vm_context: VmContext) -> Function
     Function::new_native_with_env(store, vm_context, execution_result)
 }
 
+/// Gets the length of the typed input of the current call
+pub fn call_input_length_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn call_input_length(env: &VmContext) -> Result<i64> {
+        env.check_interrupted()?;
+
+        Ok(env.call_input.len() as i64)
+    }
+
+    Function::new_native_with_env(store, vm_context, call_input_length)
+}
+
+/// Writes the typed input of the current call to the WASM memory
+pub fn call_input_write_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn call_input_write(env: &VmContext, result_data_ptr: WasmPtr<u8, Array>, result_data_length: i64) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        if result_data_length as usize != env.call_input.len() {
+            Err(format!(
+                "The result data length `{result_data_length}` is not the same length for the input `{}`",
+                env.call_input.len()
+            ))?;
+        }
+
+        let derefed_ptr = result_data_ptr
+            .deref(memory_ref, 0, result_data_length as u32)
+            .ok_or("Invalid pointer")?;
+        for (index, byte) in env.call_input.iter().enumerate() {
+            derefed_ptr
+                .get(index)
+                .ok_or("Writing out of bounds to memory")?
+                .set(*byte);
+        }
+
+        Ok(())
+    }
+
+    Function::new_native_with_env(store, vm_context, call_input_write)
+}
+
+/// Host functions that touch the node or aren't deterministic aren't allowed
+/// in a limited runtime, like the limited promises.
+fn check_not_limited(env: &VmContext, import: &str) -> Result<()> {
+    if env.host.limited {
+        Err(format!("Method `{import}` not allowed in limited runtime"))?;
+    }
+
+    Ok(())
+}
+
+/// Resolves the database namespace given by WASM, an empty namespace is the
+/// namespace of the binary itself.
+fn sync_db_namespace(env: &VmContext, namespace: String) -> Result<String> {
+    let namespace = (!namespace.is_empty()).then_some(namespace);
+
+    match db_namespace(&env.host.node_config, &env.host.program_name, &namespace) {
+        Ok(namespace) => Ok(namespace.to_string()),
+        Err(_) => Err(format!(
+            "Database namespace `{}` is not shared",
+            namespace.unwrap_or_default()
+        ))?,
+    }
+}
+
+/// Reads the value of the key right away and sends its length to WASM, `-1`
+/// when the key isn't set. The value itself is read with `host_result_write`.
+pub fn db_get_sync_length_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn db_get_sync_length(
+        env: &VmContext,
+        namespace: WasmPtr<u8, Array>,
+        namespace_length: i64,
+        key: WasmPtr<u8, Array>,
+        key_length: i64,
+    ) -> Result<i64> {
+        env.check_interrupted()?;
+
+        check_not_limited(env, "db_get_sync")?;
+        let memory_ref = get_memory(env)?;
+        let namespace = namespace
+            .get_utf8_string(memory_ref, namespace_length as u32)
+            .ok_or("Error getting the namespace")?;
+        let key = key
+            .get_utf8_string(memory_ref, key_length as u32)
+            .ok_or("Error getting the key")?;
+        let namespace = sync_db_namespace(env, namespace)?;
+
+        // The writes of the execution aren't committed yet, but it still reads them
+        let buffered = env.host.db_writes.lock().get(&namespace, &key);
+        let value = match buffered {
+            Some(value) => value,
+            None => env.host.db_get(&namespace, &key)?,
+        };
+
+        let mut host_result = env.host_result.lock();
+        match value {
+            Some(value) => {
+                *host_result = value;
+                Ok(host_result.len() as i64)
+            }
+            None => {
+                host_result.clear();
+                Ok(-1)
+            }
+        }
+    }
+
+    Function::new_native_with_env(store, vm_context, db_get_sync_length)
+}
+
+/// Stores the value under the key, it is committed with the other writes of
+/// the execution.
+pub fn db_set_sync_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn db_set_sync(
+        env: &VmContext,
+        namespace: WasmPtr<u8, Array>,
+        namespace_length: i64,
+        key: WasmPtr<u8, Array>,
+        key_length: i64,
+        value: WasmPtr<u8, Array>,
+        value_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        check_not_limited(env, "db_set_sync")?;
+        let memory_ref = get_memory(env)?;
+        let namespace = namespace
+            .get_utf8_string(memory_ref, namespace_length as u32)
+            .ok_or("Error getting the namespace")?;
+        let key = key
+            .get_utf8_string(memory_ref, key_length as u32)
+            .ok_or("Error getting the key")?;
+        let value = value
+            .deref(memory_ref, 0, value_length as u32)
+            .ok_or("Invalid pointer")?;
+        let value: Vec<u8> = value.into_iter().map(|wc| wc.get()).collect();
+        let namespace = sync_db_namespace(env, namespace)?;
+
+        env.host.db_writes.lock().set(&namespace, &key, value);
+
+        Ok(())
+    }
+
+    Function::new_native_with_env(store, vm_context, db_set_sync)
+}
+
+/// Sends the length of the [GuestConfig](seda_runtime_sdk::GuestConfig) as
+/// JSON to WASM, the config itself is read with `host_result_write`.
+pub fn config_length_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn config_length(env: &VmContext) -> Result<i64> {
+        env.check_interrupted()?;
+
+        let config = serde_json::to_vec(&env.host.guest_config)?;
+
+        let mut host_result = env.host_result.lock();
+        *host_result = config;
+
+        Ok(host_result.len() as i64)
+    }
+
+    Function::new_native_with_env(store, vm_context, config_length)
+}
+
+/// Writes the value of the last synchronous import that returned data to the
+/// WASM memory.
+pub fn host_result_write_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn host_result_write(env: &VmContext, result_data_ptr: WasmPtr<u8, Array>, result_data_length: i64) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let host_result = env.host_result.lock();
+        if result_data_length as usize != host_result.len() {
+            Err(format!(
+                "The result data length `{result_data_length}` is not the same length for the value `{}`",
+                host_result.len()
+            ))?;
+        }
+
+        let derefed_ptr = result_data_ptr
+            .deref(memory_ref, 0, result_data_length as u32)
+            .ok_or("Invalid pointer")?;
+        for (index, byte) in host_result.iter().enumerate() {
+            derefed_ptr
+                .get(index)
+                .ok_or("Writing out of bounds to memory")?
+                .set(*byte);
+        }
+
+        Ok(())
+    }
+
+    Function::new_native_with_env(store, vm_context, host_result_write)
+}
+
+/// Gets the current time in ms since the unix epoch.
+pub fn time_now_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn time_now(env: &VmContext) -> Result<i64> {
+        env.check_interrupted()?;
+
+        check_not_limited(env, "time_now")?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| "The system time is before the unix epoch")?;
+
+        Ok(now.as_millis() as i64)
+    }
+
+    Function::new_native_with_env(store, vm_context, time_now)
+}
+
+/// Fills the WASM buffer with random bytes.
+pub fn random_fill_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn random_fill(env: &VmContext, result_data_ptr: WasmPtr<u8, Array>, result_data_length: i64) -> Result<()> {
+        env.check_interrupted()?;
+
+        check_not_limited(env, "random_fill")?;
+        let memory_ref = get_memory(env)?;
+        let mut random_bytes = vec![0; result_data_length as usize];
+        rand::thread_rng().fill_bytes(&mut random_bytes);
+
+        let derefed_ptr = result_data_ptr
+            .deref(memory_ref, 0, result_data_length as u32)
+            .ok_or("Invalid pointer")?;
+        for (index, byte) in random_bytes.iter().enumerate() {
+            derefed_ptr
+                .get(index)
+                .ok_or("Writing out of bounds to memory")?
+                .set(*byte);
+        }
+
+        Ok(())
+    }
+
+    Function::new_native_with_env(store, vm_context, random_fill)
+}
+
+/// Gets the consensus time of the execution in ms since the unix epoch, the
+/// same on every node that runs the event.
+pub fn consensus_time_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn consensus_time(env: &VmContext) -> Result<i64> {
+        env.check_interrupted()?;
+
+        Ok(env.host.consensus.timestamp as i64)
+    }
+
+    Function::new_native_with_env(store, vm_context, consensus_time)
+}
+
+/// Fills the WASM buffer from the deterministic RNG of the execution, every
+/// node that runs the event draws the same bytes.
+pub fn deterministic_random_fill_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn deterministic_random_fill(
+        env: &VmContext,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let mut random_bytes = vec![0; result_data_length as usize];
+        env.host.consensus.rng.lock().fill(&mut random_bytes);
+
+        let derefed_ptr = result_data_ptr
+            .deref(memory_ref, 0, result_data_length as u32)
+            .ok_or("Invalid pointer")?;
+        for (index, byte) in random_bytes.iter().enumerate() {
+            derefed_ptr
+                .get(index)
+                .ok_or("Writing out of bounds to memory")?
+                .set(*byte);
+        }
+
+        Ok(())
+    }
+
+    Function::new_native_with_env(store, vm_context, deterministic_random_fill)
+}
+
+/// Replaces `clock_time_get` of WASI in a limited runtime, every clock reads
+/// the consensus time.
+fn wasi_clock_time_get_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn clock_time_get(env: &VmContext, _clock_id: i32, _precision: i64, time: WasmPtr<u64>) -> Result<i32> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let time = time.deref(memory_ref).ok_or("Invalid pointer")?;
+        // WASI clocks are in ns
+        match env.host.consensus.timestamp.checked_mul(1_000_000) {
+            Some(nanos) => {
+                time.set(nanos);
+                Ok(0)
+            }
+            None => Ok(__WASI_EOVERFLOW.into()),
+        }
+    }
+
+    Function::new_native_with_env(store, vm_context, clock_time_get)
+}
+
+/// Replaces `random_get` of WASI in a limited runtime with the deterministic
+/// RNG of the execution.
+fn wasi_random_get_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn random_get(env: &VmContext, buf: WasmPtr<u8, Array>, buf_len: i32) -> Result<i32> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let mut random_bytes = vec![0; buf_len as usize];
+        env.host.consensus.rng.lock().fill(&mut random_bytes);
+
+        let derefed_ptr = buf.deref(memory_ref, 0, buf_len as u32).ok_or("Invalid pointer")?;
+        for (index, byte) in random_bytes.iter().enumerate() {
+            derefed_ptr
+                .get(index)
+                .ok_or("Writing out of bounds to memory")?
+                .set(*byte);
+        }
+
+        Ok(0)
+    }
+
+    Function::new_native_with_env(store, vm_context, random_get)
+}
+
 pub fn log_import_obj(store: &Store, vm_context: VmContext) -> Function {
     fn log(
         env: &VmContext,
@@ -195,6 +575,8 @@ pub fn log_import_obj(store: &Store, vm_context: VmContext) -> Function {
         line_info: WasmPtr<u8, Array>,
         line_info_len: i64,
     ) -> Result<()> {
+        env.check_interrupted()?;
+
         let memory_ref = get_memory(env)?;
 
         let promise_data_raw = level
@@ -237,6 +619,8 @@ pub fn bn254_verify_import_obj(store: &Store, vm_context: VmContext) -> Function
         public_key: WasmPtr<u8, Array>,
         public_key_length: i64,
     ) -> Result<u8> {
+        env.check_interrupted()?;
+
         // Fetch function arguments as Vec<u8>
         let memory_ref = get_memory(env)?;
         let message = message
@@ -285,6 +669,8 @@ pub fn bn254_sign_import_obj(store: &Store, vm_context: VmContext) -> Function {
         result_data_ptr: WasmPtr<u8, Array>,
         result_data_length: i64,
     ) -> Result<()> {
+        env.check_interrupted()?;
+
         // Fetch function arguments as Vec<u8>
         let memory_ref = get_memory(env)?;
         let message = message
@@ -325,6 +711,427 @@ pub fn bn254_sign_import_obj(store: &Store, vm_context: VmContext) -> Function {
     Function::new_native_with_env(store, vm_context, bn254_sign)
 }
 
+/// Signs with ECDSA using the `bn254` key of the node, WASM never sees the
+/// key itself.
+///
+/// Inputs:
+///
+/// * `message` - The message bytes
+///
+/// Output:
+///     - Signature (a G1 point) as byte array to the wasm result pointer
+pub fn node_sign_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn node_sign(
+        env: &VmContext,
+        message: WasmPtr<u8, Array>,
+        message_length: i64,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        check_not_limited(env, "node_sign")?;
+        let memory_ref = get_memory(env)?;
+        let message = read_bytes(memory_ref, message, message_length)?;
+
+        let secret_key = &env.host.node_config.seda_secret_key;
+        if secret_key.is_empty() {
+            Err("The node has no secret key")?;
+        }
+        let secret_key = hex::decode(secret_key).map_err(|_| "The secret key of the node is not valid hex")?;
+        let private_key_obj = bn254::PrivateKey::try_from(secret_key.as_ref())?;
+        let signature = bn254::ECDSA::sign(&message, &private_key_obj)?;
+
+        write_bytes(
+            memory_ref,
+            result_data_ptr,
+            result_data_length,
+            &signature.to_compressed()?,
+        )
+    }
+
+    Function::new_native_with_env(store, vm_context, node_sign)
+}
+
+/// The length of a compressed `bn254` signature (a G1 point).
+const BN254_SIGNATURE_LENGTH: usize = 33;
+/// The length of a compressed `bn254` public key (a G2 point).
+const BN254_PUBLIC_KEY_LENGTH: usize = 65;
+
+/// Parses the concatenated compressed `bn254` public keys and adds them up.
+fn aggregate_bn254_public_keys(public_keys: &[u8]) -> Result<bn254::PublicKey> {
+    if public_keys.len() % BN254_PUBLIC_KEY_LENGTH != 0 {
+        Err(format!(
+            "The public keys length `{}` is not a multiple of `{BN254_PUBLIC_KEY_LENGTH}`",
+            public_keys.len()
+        ))?;
+    }
+
+    let mut aggregate: Option<bn254::PublicKey> = None;
+    for public_key in public_keys.chunks(BN254_PUBLIC_KEY_LENGTH) {
+        let public_key = bn254::PublicKey::from_compressed(public_key)?;
+        aggregate = Some(match aggregate {
+            Some(aggregate) => aggregate + public_key,
+            None => public_key,
+        });
+    }
+
+    Ok(aggregate.ok_or("No public keys to aggregate")?)
+}
+
+/// Aggregates `bn254` signatures of the same message into one signature, e.g.
+/// the signatures of a committee.
+///
+/// Inputs:
+///     - signatures (concatenated compressed G1 points)
+///
+/// Output:
+///     - The aggregate signature (a G1 point) to the wasm result pointer
+pub fn bn254_aggregate_signatures_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn bn254_aggregate_signatures(
+        env: &VmContext,
+        signatures: WasmPtr<u8, Array>,
+        signatures_length: i64,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let signatures = read_bytes(memory_ref, signatures, signatures_length)?;
+        if signatures.len() % BN254_SIGNATURE_LENGTH != 0 {
+            Err(format!(
+                "The signatures length `{}` is not a multiple of `{BN254_SIGNATURE_LENGTH}`",
+                signatures.len()
+            ))?;
+        }
+
+        let mut aggregate: Option<bn254::Signature> = None;
+        for signature in signatures.chunks(BN254_SIGNATURE_LENGTH) {
+            let signature = bn254::Signature::from_compressed(signature)?;
+            aggregate = Some(match aggregate {
+                Some(aggregate) => aggregate + signature,
+                None => signature,
+            });
+        }
+        let aggregate = aggregate.ok_or("No signatures to aggregate")?;
+
+        write_bytes(
+            memory_ref,
+            result_data_ptr,
+            result_data_length,
+            &aggregate.to_compressed()?,
+        )
+    }
+
+    Function::new_native_with_env(store, vm_context, bn254_aggregate_signatures)
+}
+
+/// Aggregates `bn254` public keys into the key an aggregate signature of
+/// their owners verifies against.
+///
+/// Inputs:
+///     - public_keys (concatenated compressed G2 points)
+///
+/// Output:
+///     - The aggregate public key (a G2 point) to the wasm result pointer
+pub fn bn254_aggregate_public_keys_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn bn254_aggregate_public_keys(
+        env: &VmContext,
+        public_keys: WasmPtr<u8, Array>,
+        public_keys_length: i64,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let public_keys = read_bytes(memory_ref, public_keys, public_keys_length)?;
+        let aggregate = aggregate_bn254_public_keys(&public_keys)?;
+
+        write_bytes(
+            memory_ref,
+            result_data_ptr,
+            result_data_length,
+            &aggregate.to_compressed()?,
+        )
+    }
+
+    Function::new_native_with_env(store, vm_context, bn254_aggregate_public_keys)
+}
+
+/// Verifies an aggregate `bn254` signature against the keys of all its
+/// signers.
+///
+/// The keys are aggregated as given, so the caller must only pass keys whose
+/// owners proved possession of them (e.g. registered committee keys);
+/// otherwise a rogue key can forge the aggregate signature.
+///
+/// Inputs:
+///     - message (any payload in bytes)
+///     - signature (bytes as compressed G1 point)
+///     - public_keys (concatenated compressed G2 points)
+///
+/// Output:
+///     - u8 (boolean, 1 for true)
+pub fn bn254_verify_aggregate_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn bn254_verify_aggregate(
+        env: &VmContext,
+        message: WasmPtr<u8, Array>,
+        message_length: i64,
+        signature: WasmPtr<u8, Array>,
+        signature_length: i64,
+        public_keys: WasmPtr<u8, Array>,
+        public_keys_length: i64,
+    ) -> Result<u8> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message = read_bytes(memory_ref, message, message_length)?;
+        let signature = read_bytes(memory_ref, signature, signature_length)?;
+        let public_keys = read_bytes(memory_ref, public_keys, public_keys_length)?;
+
+        let signature_obj = bn254::Signature::from_compressed(signature)?;
+        let public_key_obj = aggregate_bn254_public_keys(&public_keys)?;
+
+        Ok(bn254::ECDSA::verify(message, &signature_obj, &public_key_obj)
+            .is_ok()
+            .into())
+    }
+
+    Function::new_native_with_env(store, vm_context, bn254_verify_aggregate)
+}
+
+/// Copies a WASM buffer, the inputs of the crypto imports.
+fn read_bytes(memory_ref: &Memory, ptr: WasmPtr<u8, Array>, length: i64) -> Result<Vec<u8>> {
+    let derefed_ptr = ptr.deref(memory_ref, 0, length as u32).ok_or("Invalid pointer")?;
+
+    Ok(derefed_ptr.into_iter().map(|wc| wc.get()).collect())
+}
+
+/// Writes the result of a crypto import to the WASM buffer, which has to have
+/// the exact length of the result.
+fn write_bytes(memory_ref: &Memory, ptr: WasmPtr<u8, Array>, length: i64, bytes: &[u8]) -> Result<()> {
+    if length as usize != bytes.len() {
+        Err(format!(
+            "The result data length `{length}` is not the same length for the value `{}`",
+            bytes.len()
+        ))?;
+    }
+
+    let derefed_ptr = ptr.deref(memory_ref, 0, length as u32).ok_or("Invalid pointer")?;
+    for (index, byte) in bytes.iter().enumerate() {
+        derefed_ptr
+            .get(index)
+            .ok_or("Writing out of bounds to memory")?
+            .set(*byte);
+    }
+
+    Ok(())
+}
+
+/// Hashes the message with SHA-256.
+///
+/// Output:
+///     - The 32 byte hash to the wasm result pointer
+pub fn sha256_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn sha256(
+        env: &VmContext,
+        message: WasmPtr<u8, Array>,
+        message_length: i64,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message = read_bytes(memory_ref, message, message_length)?;
+
+        write_bytes(
+            memory_ref,
+            result_data_ptr,
+            result_data_length,
+            &Sha256::digest(message),
+        )
+    }
+
+    Function::new_native_with_env(store, vm_context, sha256)
+}
+
+/// Hashes the message with Keccak-256, as used by Ethereum.
+///
+/// Output:
+///     - The 32 byte hash to the wasm result pointer
+pub fn keccak256_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn keccak256(
+        env: &VmContext,
+        message: WasmPtr<u8, Array>,
+        message_length: i64,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<()> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message = read_bytes(memory_ref, message, message_length)?;
+
+        write_bytes(
+            memory_ref,
+            result_data_ptr,
+            result_data_length,
+            &Keccak256::digest(message),
+        )
+    }
+
+    Function::new_native_with_env(store, vm_context, keccak256)
+}
+
+/// Verifies a `secp256k1` ECDSA signature, signatures with a high `s` are
+/// accepted as well.
+///
+/// Inputs:
+///     - message_hash (the 32 byte hash of the message)
+///     - signature (64 bytes, compact encoding)
+///     - public_key (33 bytes compressed or 65 bytes uncompressed)
+///
+/// Output:
+///     - u8 (boolean, 1 for true), malformed inputs don't verify
+pub fn secp256k1_verify_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn secp256k1_verify(
+        env: &VmContext,
+        message_hash: WasmPtr<u8, Array>,
+        message_hash_length: i64,
+        signature: WasmPtr<u8, Array>,
+        signature_length: i64,
+        public_key: WasmPtr<u8, Array>,
+        public_key_length: i64,
+    ) -> Result<u8> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message_hash = read_bytes(memory_ref, message_hash, message_hash_length)?;
+        let signature = read_bytes(memory_ref, signature, signature_length)?;
+        let public_key = read_bytes(memory_ref, public_key, public_key_length)?;
+
+        let (message, mut signature, public_key) = match (
+            Message::from_slice(&message_hash),
+            Signature::from_compact(&signature),
+            PublicKey::from_slice(&public_key),
+        ) {
+            (Ok(message), Ok(signature), Ok(public_key)) => (message, signature, public_key),
+            _ => return Ok(0),
+        };
+        signature.normalize_s();
+
+        Ok(Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &public_key)
+            .is_ok()
+            .into())
+    }
+
+    Function::new_native_with_env(store, vm_context, secp256k1_verify)
+}
+
+/// Recovers the `secp256k1` public key that signed the message hash, e.g. to
+/// derive the Ethereum address of the signer.
+///
+/// Inputs:
+///     - message_hash (the 32 byte hash of the message)
+///     - signature (64 bytes, compact encoding)
+///     - recovery_id (0 to 3)
+///
+/// Output:
+///     - u8 (boolean, 1 when a public key was recovered)
+///     - The public key to the wasm result pointer, compressed for a 33 byte
+///       result and uncompressed for a 65 byte result
+pub fn secp256k1_recover_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    #[allow(clippy::too_many_arguments)]
+    fn secp256k1_recover(
+        env: &VmContext,
+        message_hash: WasmPtr<u8, Array>,
+        message_hash_length: i64,
+        signature: WasmPtr<u8, Array>,
+        signature_length: i64,
+        recovery_id: i32,
+        result_data_ptr: WasmPtr<u8, Array>,
+        result_data_length: i64,
+    ) -> Result<u8> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message_hash = read_bytes(memory_ref, message_hash, message_hash_length)?;
+        let signature = read_bytes(memory_ref, signature, signature_length)?;
+
+        let public_key = RecoveryId::from_i32(recovery_id)
+            .and_then(|recovery_id| RecoverableSignature::from_compact(&signature, recovery_id))
+            .and_then(|signature| {
+                let message = Message::from_slice(&message_hash)?;
+                Secp256k1::verification_only().recover_ecdsa(&message, &signature)
+            });
+        let public_key = match public_key {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(0),
+        };
+
+        match result_data_length {
+            33 => write_bytes(memory_ref, result_data_ptr, result_data_length, &public_key.serialize())?,
+            65 => write_bytes(
+                memory_ref,
+                result_data_ptr,
+                result_data_length,
+                &public_key.serialize_uncompressed(),
+            )?,
+            _ => Err(format!(
+                "The result data length `{result_data_length}` is not the length of a public key"
+            ))?,
+        }
+
+        Ok(1)
+    }
+
+    Function::new_native_with_env(store, vm_context, secp256k1_recover)
+}
+
+/// Verifies an `ed25519` signature.
+///
+/// Inputs:
+///     - message (any payload in bytes)
+///     - signature (64 bytes)
+///     - public_key (32 bytes)
+///
+/// Output:
+///     - u8 (boolean, 1 for true), malformed inputs don't verify
+pub fn ed25519_verify_import_obj(store: &Store, vm_context: VmContext) -> Function {
+    fn ed25519_verify(
+        env: &VmContext,
+        message: WasmPtr<u8, Array>,
+        message_length: i64,
+        signature: WasmPtr<u8, Array>,
+        signature_length: i64,
+        public_key: WasmPtr<u8, Array>,
+        public_key_length: i64,
+    ) -> Result<u8> {
+        env.check_interrupted()?;
+
+        let memory_ref = get_memory(env)?;
+        let message = read_bytes(memory_ref, message, message_length)?;
+        let signature = read_bytes(memory_ref, signature, signature_length)?;
+        let public_key = read_bytes(memory_ref, public_key, public_key_length)?;
+
+        let (signature, public_key) = match (
+            ed25519_dalek::Signature::try_from(signature.as_slice()),
+            ed25519_dalek::PublicKey::from_bytes(&public_key),
+        ) {
+            (Ok(signature), Ok(public_key)) => (signature, public_key),
+            _ => return Ok(0),
+        };
+
+        Ok(public_key.verify(&message, &signature).is_ok().into())
+    }
+
+    Function::new_native_with_env(store, vm_context, ed25519_verify)
+}
+
 // Creates the WASM function imports with the stringed names.
 pub fn create_wasm_imports(
     store: &Store,
@@ -332,23 +1139,62 @@ pub fn create_wasm_imports(
     wasi_env: &mut WasiEnv,
     wasm_module: &Module,
 ) -> Result<ImportObject> {
+    let mut wasi_import_obj = wasi_env.import_object(wasm_module)?;
+
+    // The results of a limited runtime have to match across nodes, so its binaries
+    // don't get the clock and entropy of the host
+    if vm_context.host.limited {
+        let wasi_namespace = get_wasi_version(wasm_module, false)
+            .ok_or("Unknown WASI version")?
+            .get_namespace_str();
+        let mut wasi_exports = wasi_import_obj
+            .get_namespace_exports(wasi_namespace)
+            .ok_or("VM could not get WASI namespace")?;
+        wasi_exports.insert(
+            "clock_time_get",
+            wasi_clock_time_get_import_obj(store, vm_context.clone()),
+        );
+        wasi_exports.insert("random_get", wasi_random_get_import_obj(store, vm_context.clone()));
+        wasi_import_obj.register(wasi_namespace, wasi_exports);
+    }
+
     let host_import_obj = imports! {
         "env" => {
             "promise_then" => promise_then_import_obj(store, vm_context.clone()),
             "promise_status_length" => promise_status_length_import_obj(store, vm_context.clone()),
             "promise_status_write" => promise_status_write_import_obj(store, vm_context.clone()),
+            "promise_status_length_v1" => promise_status_length_v1_import_obj(store, vm_context.clone()),
+            "promise_status_write_v1" => promise_status_write_v1_import_obj(store, vm_context.clone()),
             "memory_read" => memory_read_import_obj(store, vm_context.clone()),
             "memory_read_length" => memory_read_length_import_obj(store, vm_context.clone()),
             "memory_write" => memory_write_import_obj(store, vm_context.clone()),
             "execution_result" => execution_result_import_obj(store, vm_context.clone()),
+            "call_input_length" => call_input_length_import_obj(store, vm_context.clone()),
+            "call_input_write" => call_input_write_import_obj(store, vm_context.clone()),
+            "db_get_sync_length" => db_get_sync_length_import_obj(store, vm_context.clone()),
+            "db_set_sync" => db_set_sync_import_obj(store, vm_context.clone()),
+            "config_length" => config_length_import_obj(store, vm_context.clone()),
+            "host_result_write" => host_result_write_import_obj(store, vm_context.clone()),
+            "time_now" => time_now_import_obj(store, vm_context.clone()),
+            "random_fill" => random_fill_import_obj(store, vm_context.clone()),
+            "consensus_time" => consensus_time_import_obj(store, vm_context.clone()),
+            "deterministic_random_fill" => deterministic_random_fill_import_obj(store, vm_context.clone()),
             "_log" => log_import_obj(store, vm_context.clone()),
             "bn254_verify" => bn254_verify_import_obj(store, vm_context.clone()),
-            "bn254_sign" => bn254_sign_import_obj(store, vm_context)
+            "bn254_sign" => bn254_sign_import_obj(store, vm_context.clone()),
+            "node_sign" => node_sign_import_obj(store, vm_context.clone()),
+            "bn254_aggregate_signatures" => bn254_aggregate_signatures_import_obj(store, vm_context.clone()),
+            "bn254_aggregate_public_keys" => bn254_aggregate_public_keys_import_obj(store, vm_context.clone()),
+            "bn254_verify_aggregate" => bn254_verify_aggregate_import_obj(store, vm_context.clone()),
+            "sha256" => sha256_import_obj(store, vm_context.clone()),
+            "keccak256" => keccak256_import_obj(store, vm_context.clone()),
+            "secp256k1_verify" => secp256k1_verify_import_obj(store, vm_context.clone()),
+            "secp256k1_recover" => secp256k1_recover_import_obj(store, vm_context.clone()),
+            "ed25519_verify" => ed25519_verify_import_obj(store, vm_context)
         }
     };
 
     // Combining the WASI exports with our custom (host) imports
-    let mut wasi_import_obj = wasi_env.import_object(wasm_module)?;
     let host_exports = host_import_obj
         .get_namespace_exports("env")
         .ok_or("VM could not get env namespace")?;
@@ -1,11 +1,20 @@
 //! WASI compatible WASM VM
 
+// Lets `#[derive(Adapter)]` name this crate by its path from inside it too
+extern crate self as seda_runtime;
+
+mod cancellation_token;
+pub use cancellation_token::*;
+
 mod config;
 pub use config::*;
 
 mod context;
 pub use context::*;
 
+mod deterministic;
+pub use deterministic::*;
+
 mod errors;
 pub use errors::*;
 
@@ -14,6 +23,13 @@ pub use host_adapter::*;
 
 pub(crate) mod imports;
 
+pub(crate) mod metering;
+
+mod module_cache;
+pub use module_cache::*;
+
+pub(crate) mod tunables;
+
 mod promise;
 pub(crate) use promise::*;
 
@@ -23,6 +39,9 @@ pub use runtime::*;
 mod storage;
 pub use storage::*;
 
+mod trace;
+pub use trace::*;
+
 mod vm_result;
 pub use vm_result::*;
 
@@ -32,5 +51,6 @@ mod test {
     mod test_host;
     pub(crate) use test_host::*;
 
+    mod deterministic_test;
     mod runtime_test;
 }
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use wasmer::{wasmparser::Operator, CompilerConfig, Cranelift, Instance, Store, Tunables, Universal};
+use wasmer_middlewares::{
+    metering::{get_remaining_points, set_remaining_points, MeteringPoints},
+    Metering,
+};
+
+/// Every operator costs the same amount of gas, this keeps the metering
+/// deterministic across nodes regardless of the host machine.
+fn cost_function(_operator: &Operator) -> u64 {
+    1
+}
+
+/// Creates a [Store] where every compiled module is instrumented with gas
+/// metering. The actual limit is set per instance before it gets executed.
+pub fn create_metered_store(tunables: impl Tunables + Send + Sync + 'static) -> Store {
+    let metering = Arc::new(Metering::new(u64::MAX, cost_function));
+    let mut compiler_config = Cranelift::default();
+    compiler_config.push_middleware(metering);
+
+    Store::new_with_tunables(&Universal::new(compiler_config).engine(), tunables)
+}
+
+/// Sets the amount of gas the instance is allowed to consume.
+pub fn set_remaining_gas(instance: &Instance, gas: u64) {
+    set_remaining_points(instance, gas);
+}
+
+/// Returns the gas left on the instance or `None` when it ran out of gas.
+pub fn get_remaining_gas(instance: &Instance) -> Option<u64> {
+    match get_remaining_points(instance) {
+        MeteringPoints::Remaining(gas) => Some(gas),
+        MeteringPoints::Exhausted => None,
+    }
+}
@@ -0,0 +1,156 @@
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+
+use parking_lot::Mutex;
+use seda_config::WasmBinarySource;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use wasmer::{ExternType, Module, Store};
+
+use crate::{metering::create_metered_store, tunables::LimitingTunables, Result, RuntimeError};
+
+/// The sha256 hash of the binary, used as the key of the cache.
+pub fn hash_wasm_binary(wasm_binary: &[u8]) -> String {
+    hex::encode(Sha256::digest(wasm_binary))
+}
+
+/// Caches compiled WASM modules by the sha256 hash of their binary. Compiled
+/// modules are kept in memory and, when a cache directory is given, written to
+/// disk as serialized wasmer artifacts so a restarted node doesn't have to
+/// compile them again.
+///
+/// Cloning the cache is cheap, all clones share the same modules.
+#[derive(Clone)]
+pub struct ModuleCache {
+    store:     Store,
+    modules:   Arc<Mutex<HashMap<String, Module>>>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl ModuleCache {
+    pub fn new(max_memory_pages: u32, cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            store: create_metered_store(LimitingTunables::new(max_memory_pages)),
+            modules: Default::default(),
+            cache_dir,
+        }
+    }
+
+    /// Returns the compiled module of the binary, the binary is only compiled
+    /// when it isn't in memory or on disk yet.
+    pub fn get_or_compile(&self, wasm_binary: &[u8]) -> Result<Module> {
+        let hash = hash_wasm_binary(wasm_binary);
+
+        // The lock is held while compiling, so workers that start at the same
+        // time don't compile the same binary twice
+        let mut modules = self.modules.lock();
+        if let Some(module) = modules.get(&hash) {
+            return Ok(module.clone());
+        }
+
+        let module = match self.deserialize(&hash) {
+            Some(module) => module,
+            None => {
+                info!("Compiling WASM module {hash}");
+                let module = Module::new(&self.store, wasm_binary)?;
+                self.serialize(&hash, &module);
+                module
+            }
+        };
+
+        modules.insert(hash, module.clone());
+
+        Ok(module)
+    }
+
+    /// Returns the module of a binary that was compiled before, by this node
+    /// or by a previous run that left the artifact on disk.
+    pub fn get(&self, hash: &str) -> Result<Module> {
+        let mut modules = self.modules.lock();
+        if let Some(module) = modules.get(hash) {
+            return Ok(module.clone());
+        }
+
+        let module = self
+            .deserialize(hash)
+            .ok_or_else(|| RuntimeError::ModuleNotCached(hash.to_string()))?;
+        modules.insert(hash.to_string(), module.clone());
+
+        Ok(module)
+    }
+
+    /// Loads the binary into the cache and checks that the VM is able to run
+    /// it, returns the hash to get the module with.
+    pub fn load(&self, source: &WasmBinarySource) -> Result<String> {
+        let (hash, module) = match source {
+            WasmBinarySource::Path(path) => {
+                let wasm_binary = fs::read(path)?;
+                (hash_wasm_binary(&wasm_binary), self.get_or_compile(&wasm_binary)?)
+            }
+            WasmBinarySource::Hash(hash) => (hash.clone(), self.get(hash)?),
+        };
+
+        validate_exports(&module)?;
+
+        Ok(hash)
+    }
+
+    fn artifact_path(&self, hash: &str) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|cache_dir| cache_dir.join(format!("{hash}.wasmu")))
+    }
+
+    fn deserialize(&self, hash: &str) -> Option<Module> {
+        let path = self.artifact_path(hash).filter(|path| path.exists())?;
+
+        // SAFETY: the artifacts in the cache directory are only written by
+        // `serialize`, the file name is the hash of the original binary.
+        match unsafe { Module::deserialize_from_file(&self.store, &path) } {
+            Ok(module) => Some(module),
+            Err(err) => {
+                // Artifacts of another wasmer version can't be loaded, compile them again
+                warn!("Failed to load cached WASM module {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    fn serialize(&self, hash: &str, module: &Module) {
+        let (Some(cache_dir), Some(path)) = (&self.cache_dir, self.artifact_path(hash)) else {
+            return;
+        };
+
+        if let Err(err) = fs::create_dir_all(cache_dir) {
+            warn!(
+                "Failed to create the WASM cache directory {}: {err}",
+                cache_dir.display()
+            );
+            return;
+        }
+
+        if let Err(err) = module.serialize_to_file(&path) {
+            warn!("Failed to cache WASM module {}: {err}", path.display());
+        }
+    }
+}
+
+/// Makes sure the module exports the memory and the WASI entrypoint.
+fn validate_exports(module: &Module) -> Result<()> {
+    let has_memory = module
+        .exports()
+        .any(|export| export.name() == "memory" && matches!(export.ty(), ExternType::Memory(_)));
+    if !has_memory {
+        return Err(RuntimeError::InvalidWasmBinary("missing `memory` export".to_string()));
+    }
+
+    let has_start = module
+        .exports()
+        .any(|export| export.name() == "_start" && matches!(export.ty(), ExternType::Function(_)));
+    if !has_start {
+        return Err(RuntimeError::InvalidWasmBinary(
+            "missing `_start` function export".to_string(),
+        ));
+    }
+
+    Ok(())
+}
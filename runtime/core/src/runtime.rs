@@ -1,18 +1,40 @@
-use std::{io::Read, sync::Arc};
+use std::{
+    future::Future,
+    io::Read,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use futures::future::join_all;
 use parking_lot::Mutex;
 use seda_config::{ChainConfigs, NodeConfig};
-use seda_runtime_sdk::{p2p::P2PCommand, CallSelfAction, FromBytes, Promise, PromiseAction, PromiseStatus};
-use tokio::sync::mpsc::Sender;
+use seda_runtime_sdk::{
+    p2p::P2PEnvelope,
+    CallSelfAction,
+    GuestConfig,
+    Promise,
+    PromiseAction,
+    PromiseCondition,
+    PromiseStatus,
+};
+use tokio::time::timeout;
 use tracing::info;
 use wasmer::{Instance, Module, Store};
 use wasmer_wasi::{Pipe, WasiState};
 
 use super::{imports::create_wasm_imports, PromiseQueue, Result, VmConfig, VmContext};
 use crate::{
+    metering::{get_remaining_gas, set_remaining_gas},
+    tunables::{memory_limit_exceeded, reset_memory_limit_exceeded},
     vm_result::{ExecutionResult, ExitInfo, VmResult, VmResultStatus},
+    CancellationToken,
+    ConsensusContext,
+    DatabaseWriteBuffer,
+    ExecutionTrace,
     HostAdapter,
+    HostContext,
     InMemory,
+    ModuleCache,
     RuntimeError,
 };
 
@@ -20,8 +42,10 @@ use crate::{
 pub struct Runtime<HA: HostAdapter> {
     wasm_module:      Option<Module>,
     limited:          bool,
-    pub host_adapter: HA,
+    pub host_adapter: Arc<HA>,
     pub node_config:  NodeConfig,
+    /// What the binaries see of the node and chain configs
+    guest_config:     GuestConfig,
 }
 
 #[async_trait::async_trait]
@@ -30,11 +54,13 @@ pub trait RunnableRuntime {
     where
         Self: Sized;
     fn init(&mut self, wasm_binary: Vec<u8>) -> Result<()>;
+    fn init_with_cache(&mut self, wasm_hash: &str, module_cache: &ModuleCache) -> Result<()>;
 
     #[allow(clippy::too_many_arguments)]
     async fn execute_promise_queue(
         &self,
         wasm_module: &Module,
+        program_name: &str,
         memory_adapter: Arc<Mutex<InMemory>>,
         promise_queue: PromiseQueue,
         stdout: &mut Vec<String>,
@@ -43,37 +69,89 @@ pub trait RunnableRuntime {
         // Used to get the result of the last execution (for JSON RPC)
         // Can also be used to debug the queue
         promise_queue_trace: &mut Vec<PromiseQueue>,
-        p2p_command_sender_channel: Sender<P2PCommand>,
+        // The actions, statuses, timings and outputs of every executed promise,
+        // see `VmConfig::debug`
+        execution_trace: &mut ExecutionTrace,
+        // The gas left for the remaining WASM executions, shared across the whole
+        // promise queue
+        remaining_gas: &mut u64,
+        // The moment the whole job has to be finished by
+        deadline: Instant,
+        cancellation_token: CancellationToken,
+        // The database writes that are committed once the whole promise queue
+        // succeeded
+        db_writes: &mut DatabaseWriteBuffer,
+        consensus: &ConsensusContext,
     ) -> ExecutionResult;
 
-    async fn start_runtime(
+    async fn start_runtime(&self, config: VmConfig, memory_adapter: Arc<Mutex<InMemory>>) -> VmResult;
+}
+
+impl<HA: HostAdapter> Runtime<HA> {
+    /// Executes an action that doesn't depend on the rest of the promise
+    /// queue, see [PromiseAction::is_independent_action].
+    async fn execute_independent_action(
         &self,
-        config: VmConfig,
-        memory_adapter: Arc<Mutex<InMemory>>,
-        p2p_command_sender_channel: Sender<P2PCommand>,
-    ) -> VmResult;
+        action: &PromiseAction,
+        promise_timeout: Duration,
+        deadline: Instant,
+    ) -> ExecutionResult<PromiseStatus> {
+        match action {
+            PromiseAction::Http(http_action) => {
+                let http_fetch = async { self.host_adapter.http_fetch(http_action.clone()).await.into() };
+                with_timeout(action, http_fetch, promise_timeout, deadline).await
+            }
+            PromiseAction::ChainView(chain_view_action) => {
+                let chain_view = async {
+                    self.host_adapter
+                        .chain_view(
+                            chain_view_action.chain,
+                            &chain_view_action.contract_id,
+                            &chain_view_action.method_name,
+                            chain_view_action.args.clone(),
+                        )
+                        .await
+                        .into()
+                };
+                with_timeout(action, chain_view, promise_timeout, deadline).await
+            }
+            action => unreachable!("`{action}` is not an independent action"),
+        }
+    }
 }
 
 #[async_trait::async_trait]
 impl<HA: HostAdapter> RunnableRuntime for Runtime<HA> {
     async fn new(node_config: NodeConfig, chains_config: ChainConfigs, limited: bool) -> Result<Self> {
+        let guest_config = GuestConfig::new(&node_config, &chains_config);
+
         Ok(Self {
             wasm_module: None,
             limited,
-            host_adapter: HA::new(chains_config)
-                .await
-                .map_err(|e| RuntimeError::NodeError(e.to_string()))?,
+            host_adapter: Arc::new(
+                HA::new(chains_config)
+                    .await
+                    .map_err(|e| RuntimeError::NodeError(e.to_string()))?,
+            ),
             node_config,
+            guest_config,
         })
     }
 
     /// Initializes the runtime, this speeds up VM execution by caching WASM
-    /// binary parsing
+    /// binary parsing. The module is instrumented with gas metering and the
+    /// memory of its instances is limited.
     fn init(&mut self, wasm_binary: Vec<u8>) -> Result<()> {
-        let wasm_store = Store::default();
-        let wasm_module = Module::new(&wasm_store, wasm_binary)?;
+        let module_cache = ModuleCache::new(self.node_config.max_memory_pages, None);
+        self.wasm_module = Some(module_cache.get_or_compile(&wasm_binary)?);
 
-        self.wasm_module = Some(wasm_module);
+        Ok(())
+    }
+
+    /// Initializes the runtime with a module that was loaded into the cache
+    /// before, see [ModuleCache::load].
+    fn init_with_cache(&mut self, wasm_hash: &str, module_cache: &ModuleCache) -> Result<()> {
+        self.wasm_module = Some(module_cache.get(wasm_hash)?);
 
         Ok(())
     }
@@ -81,17 +159,34 @@ impl<HA: HostAdapter> RunnableRuntime for Runtime<HA> {
     async fn execute_promise_queue(
         &self,
         wasm_module: &Module,
+        program_name: &str,
         memory_adapter: Arc<Mutex<InMemory>>,
         promise_queue: PromiseQueue,
         stdout: &mut Vec<String>,
         stderr: &mut Vec<String>,
         promise_queue_trace: &mut Vec<PromiseQueue>,
-        p2p_command_sender_channel: Sender<P2PCommand>,
+        execution_trace: &mut ExecutionTrace,
+        remaining_gas: &mut u64,
+        deadline: Instant,
+        cancellation_token: CancellationToken,
+        db_writes: &mut DatabaseWriteBuffer,
+        consensus: &ConsensusContext,
     ) -> ExecutionResult {
-        let mut next_promise_queue = PromiseQueue::new();
-        let mut promise_queue_mut = promise_queue.clone();
+        let mut promise_queue = promise_queue;
+        let mut generations = 0;
+        // Only a binary in limited mode is held to its capabilities
+        let capabilities = self.limited.then(|| self.node_config.capabilities(program_name));
+        let missing_capability = |action: &PromiseAction| {
+            capabilities
+                .as_ref()
+                .and_then(|capabilities| action.missing_capability(capabilities, program_name))
+        };
+
+        // Every `then` of the program schedules the next generation of promises
+        loop {
+            let mut next_promise_queue = PromiseQueue::new();
+            let mut promise_queue_mut = promise_queue.clone();
 
-        {
             // This queue will be used in the current execution
             // We should not use the same promise_queue otherwise getting results back would
             // be hard to do due the indexes of results (will be hard to refactor)
@@ -99,177 +194,373 @@ impl<HA: HostAdapter> RunnableRuntime for Runtime<HA> {
                 return VmResultStatus::EmptyQueue.into();
             }
 
-            for index in 0..promise_queue.queue.len() {
-                promise_queue_mut.queue[index].status = PromiseStatus::Pending;
+            if generations == self.node_config.max_promise_generations {
+                return VmResultStatus::MaxPromiseGenerationsExceeded.into();
+            }
+            generations += 1;
+            execution_trace.next_queue();
 
-                match &promise_queue.queue[index].action {
-                    action if self.limited && action.is_limited_action() => {
-                        promise_queue_mut.queue[index].status = PromiseStatus::Rejected(
-                            format!("Method `{action}` not allowed in limited runtime").into_bytes(),
-                        )
-                    }
-                    // TODO need an ok_or type situation here. if its ok continue otherwise reject
-                    // promise? or maybe it should return a VMResult. Might hold off on this till the VMResult changes.
-                    PromiseAction::CallSelf(call_action) => {
-                        let wasm_store = Store::default();
-
-                        let stdout_pipe = Pipe::new();
-                        let stderr_pipe = Pipe::new();
-
-                        let mut wasi_env = WasiState::new(&call_action.function_name)
-                            .env(
-                                "WASM_NODE_CONFIG",
-                                serde_json::to_string(&self.node_config)
-                                    .map_err(|_| VmResultStatus::FailedToSetConfig)?,
-                            )
-                            .args(call_action.args.clone())
-                            .stdout(Box::new(stdout_pipe))
-                            .stderr(Box::new(stderr_pipe))
-                            .finalize()
-                            .map_err(|_| VmResultStatus::WasiEnvInitializeFailure)?;
-
-                        let current_promise_queue = Arc::new(Mutex::new(promise_queue_mut.clone()));
-                        let next_queue = Arc::new(Mutex::new(PromiseQueue::new()));
-
-                        let vm_context = VmContext::create_vm_context(
-                            memory_adapter.clone(),
-                            current_promise_queue,
-                            next_queue.clone(),
+            let promise_timeout = Duration::from_millis(self.node_config.promise_timeout_ms);
+
+            let mut index = 0;
+            while index < promise_queue.queue.len() {
+                if cancellation_token.is_cancelled() {
+                    return VmResultStatus::Cancelled.into();
+                }
+
+                if Instant::now() >= deadline {
+                    return VmResultStatus::Timeout.into();
+                }
+
+                // A promise whose condition isn't met is skipped, it passes the status of the
+                // previous promise on to the promises after it
+                if index > 0 {
+                    let previous_status = promise_queue_mut.queue[index - 1].status.clone();
+                    if !promise_queue.queue[index].condition.is_met(&previous_status) {
+                        promise_queue_mut.queue[index].status = previous_status;
+
+                        let skipped = Instant::now();
+                        execution_trace.record(
+                            &promise_queue_mut.queue[index],
+                            skipped,
+                            skipped,
+                            String::new(),
+                            String::new(),
                         );
+                        index += 1;
+                        continue;
+                    }
+                }
+
+                // Independent promises that are queued together run at the same time, their
+                // results still end up at their own index of the queue. Conditional promises
+                // depend on the promise before them so they don't join.
+                let independent = promise_queue.queue[index..]
+                    .iter()
+                    .take_while(|promise| {
+                        let action = &promise.action;
+                        promise.condition == PromiseCondition::Always
+                            && action.is_independent_action()
+                            && missing_capability(action).is_none()
+                    })
+                    .count();
+                if independent > 1 {
+                    let actions = promise_queue.queue[index..index + independent]
+                        .iter()
+                        .map(|promise| async {
+                            let started = Instant::now();
+                            let status = self
+                                .execute_independent_action(&promise.action, promise_timeout, deadline)
+                                .await;
+
+                            (status, started, Instant::now())
+                        });
+                    for (offset, (status, started, finished)) in join_all(actions).await.into_iter().enumerate() {
+                        let promise = &mut promise_queue_mut.queue[index + offset];
+                        let outcome = status.map(|status| promise.status = status);
+                        execution_trace.record(promise, started, finished, String::new(), String::new());
+                        outcome?;
+                    }
+
+                    index += independent;
+                    continue;
+                }
 
-                        let imports = create_wasm_imports(&wasm_store, vm_context.clone(), &mut wasi_env, wasm_module)
-                            .map_err(|_| VmResultStatus::FailedToCreateVMImports)?;
-                        let wasmer_instance = Instance::new(wasm_module, &imports)
-                            .map_err(|_| VmResultStatus::FailedToCreateWasmerInstance)?;
-                        let main_func = wasmer_instance
-                            .exports
-                            .get_function(&call_action.function_name)
-                            .map_err(|_| VmResultStatus::FailedToGetWASMFn)?;
-                        let runtime_result = main_func.call(&[]);
-
-                        let mut wasi_state = wasi_env.state();
-                        let wasi_stdout = wasi_state
-                            .fs
-                            .stdout_mut()
-                            .map_err(|_| VmResultStatus::FailedToGetWASMStdout)?
-                            .as_mut()
-                            .unwrap();
-                        let mut stdout_buffer = String::new();
-                        wasi_stdout
-                            .read_to_string(&mut stdout_buffer)
-                            .map_err(|_| VmResultStatus::FailedToConvertVMPipeToString)?;
-                        if !stdout_buffer.is_empty() {
-                            stdout.push(stdout_buffer);
+                promise_queue_mut.queue[index].status = PromiseStatus::Pending;
+                let promise_started = Instant::now();
+                let mut promise_stdout = String::new();
+                let mut promise_stderr = String::new();
+
+                let action = &promise_queue.queue[index].action;
+                let missing_capability = missing_capability(action);
+                // A promise that stops the execution is still recorded in the trace before the
+                // execution returns
+                let outcome: ExecutionResult<Option<VmResultStatus>> = async {
+                    match action {
+                        action if missing_capability.is_some() => {
+                            promise_queue_mut.queue[index].status = PromiseStatus::Rejected(
+                                format!(
+                                    "Method `{action}` not allowed in limited runtime, missing the capability for {}",
+                                    missing_capability.unwrap_or_default()
+                                )
+                                .into_bytes(),
+                            )
+                        }
+                        // TODO need an ok_or type situation here. if its ok continue otherwise reject
+                        // promise? or maybe it should return a VMResult. Might hold off on this till the VMResult
+                        // changes.
+                        PromiseAction::CallSelf(call_action) => {
+                            let wasm_store = Store::default();
+
+                            let stdout_pipe = Pipe::new();
+                            let stderr_pipe = Pipe::new();
+
+                            let mut wasi_env = WasiState::new(&call_action.function_name)
+                                .env(
+                                    "WASM_NODE_CONFIG",
+                                    serde_json::to_string(&self.guest_config)
+                                        .map_err(|_| VmResultStatus::FailedToSetConfig)?,
+                                )
+                                .args(call_action.args.clone())
+                                .stdout(Box::new(stdout_pipe))
+                                .stderr(Box::new(stderr_pipe))
+                                .finalize()
+                                .map_err(|_| VmResultStatus::WasiEnvInitializeFailure)?;
+
+                            let current_promise_queue = Arc::new(Mutex::new(promise_queue_mut.clone()));
+                            let next_queue = Arc::new(Mutex::new(PromiseQueue::new()));
+                            // Lent to the synchronous imports for the duration of the call
+                            let shared_db_writes = Arc::new(Mutex::new(std::mem::take(db_writes)));
+                            let host_context = HostContext {
+                                host_adapter: self.host_adapter.clone(),
+                                node_config: self.node_config.clone(),
+                                guest_config: self.guest_config.clone(),
+                                program_name: program_name.to_string(),
+                                limited: self.limited,
+                                db_writes: shared_db_writes.clone(),
+                                consensus: consensus.clone(),
+                                deadline,
+                                cancellation_token: cancellation_token.clone(),
+                            };
+
+                            let vm_context = VmContext::create_vm_context(
+                                memory_adapter.clone(),
+                                current_promise_queue,
+                                next_queue.clone(),
+                                call_action.input.clone(),
+                                host_context,
+                            );
+
+                            let imports =
+                                create_wasm_imports(&wasm_store, vm_context.clone(), &mut wasi_env, wasm_module)
+                                    .map_err(|_| VmResultStatus::FailedToCreateVMImports)?;
+                            reset_memory_limit_exceeded();
+                            let wasmer_instance = Instance::new(wasm_module, &imports).map_err(|_| {
+                                if memory_limit_exceeded() {
+                                    VmResultStatus::MemoryLimitExceeded
+                                } else {
+                                    VmResultStatus::FailedToCreateWasmerInstance
+                                }
+                            })?;
+                            let main_func = wasmer_instance
+                                .exports
+                                .get_function(&call_action.function_name)
+                                .map_err(|_| VmResultStatus::FailedToGetWASMFn)?;
+
+                            set_remaining_gas(&wasmer_instance, *remaining_gas);
+                            let runtime_result = main_func.call(&[]);
+                            *db_writes = std::mem::take(&mut *shared_db_writes.lock());
+                            let gas_left = get_remaining_gas(&wasmer_instance);
+                            *remaining_gas = gas_left.unwrap_or_default();
+
+                            let mut wasi_state = wasi_env.state();
+                            let wasi_stdout = wasi_state
+                                .fs
+                                .stdout_mut()
+                                .map_err(|_| VmResultStatus::FailedToGetWASMStdout)?
+                                .as_mut()
+                                .unwrap();
+                            let mut stdout_buffer = String::new();
+                            wasi_stdout
+                                .read_to_string(&mut stdout_buffer)
+                                .map_err(|_| VmResultStatus::FailedToConvertVMPipeToString)?;
+                            if !stdout_buffer.is_empty() {
+                                stdout.push(stdout_buffer.clone());
+                            }
+                            promise_stdout = stdout_buffer;
+
+                            let wasi_stderr = wasi_state
+                                .fs
+                                .stderr_mut()
+                                .map_err(|_| VmResultStatus::FailedToGetWASMStderr)?
+                                .as_mut()
+                                .unwrap();
+                            let mut stderr_buffer = String::new();
+                            wasi_stderr
+                                .read_to_string(&mut stderr_buffer)
+                                .map_err(|_| VmResultStatus::FailedToGetWASMStderr)?;
+                            if !stderr_buffer.is_empty() {
+                                stderr.push(stderr_buffer.clone());
+                            }
+                            promise_stderr = stderr_buffer;
+
+                            if gas_left.is_none() {
+                                info!("WASM ran out of gas, error output: {:?}", &stderr);
+                                return Ok(Some(VmResultStatus::OutOfGas));
+                            }
+
+                            // An import interrupted the call
+                            if runtime_result.is_err() && cancellation_token.is_cancelled() {
+                                return Ok(Some(VmResultStatus::Cancelled));
+                            }
+
+                            if runtime_result.is_err() && Instant::now() >= deadline {
+                                return Ok(Some(VmResultStatus::Timeout));
+                            }
+
+                            if runtime_result.is_err() && memory_limit_exceeded() {
+                                info!("WASM exceeded the memory limit, error output: {:?}", &stderr);
+                                return Ok(Some(VmResultStatus::MemoryLimitExceeded));
+                            }
+
+                            if let Err(err) = runtime_result {
+                                info!("WASM Error output: {:?}", &stderr);
+                                return Ok(Some(VmResultStatus::ExecutionError(err.to_string())));
+                            }
+
+                            let execution_result = vm_context.result.lock();
+                            next_promise_queue = next_queue.lock().clone();
+                            promise_queue_mut.queue[index].status =
+                                PromiseStatus::Fulfilled(Some(execution_result.clone()));
                         }
 
-                        let wasi_stderr = wasi_state
-                            .fs
-                            .stderr_mut()
-                            .map_err(|_| VmResultStatus::FailedToGetWASMStderr)?
-                            .as_mut()
-                            .unwrap();
-                        let mut stderr_buffer = String::new();
-                        wasi_stderr
-                            .read_to_string(&mut stderr_buffer)
-                            .map_err(|_| VmResultStatus::FailedToGetWASMStderr)?;
-                        if !stderr_buffer.is_empty() {
-                            stderr.push(stderr_buffer);
+                        PromiseAction::DatabaseSet(db_action) => {
+                            promise_queue_mut.queue[index].status =
+                                match db_namespace(&self.node_config, program_name, &db_action.namespace) {
+                                    Ok(namespace) if db_action.immediate => {
+                                        db_writes.remove(namespace, &db_action.key);
+                                        let db_set = async {
+                                            self.host_adapter
+                                                .db_set(namespace, &db_action.key, &db_action.value)
+                                                .await
+                                                .into()
+                                        };
+                                        with_timeout(action, db_set, promise_timeout, deadline).await?
+                                    }
+                                    Ok(namespace) => {
+                                        db_writes.set(namespace, &db_action.key, db_action.value.clone());
+                                        PromiseStatus::Fulfilled(Some(Vec::new()))
+                                    }
+                                    Err(rejection) => rejection,
+                                };
                         }
 
-                        if let Err(err) = runtime_result {
-                            info!("WASM Error output: {:?}", &stderr);
-                            return VmResultStatus::ExecutionError(err.to_string()).into();
+                        PromiseAction::DatabaseGet(db_action) => {
+                            promise_queue_mut.queue[index].status =
+                                match db_namespace(&self.node_config, program_name, &db_action.namespace) {
+                                    Ok(namespace) => match db_writes.get(namespace, &db_action.key) {
+                                        Some(value) => PromiseStatus::Fulfilled(value),
+                                        None => {
+                                            let db_get = async {
+                                                self.host_adapter.db_get(namespace, &db_action.key).await.into()
+                                            };
+                                            with_timeout(action, db_get, promise_timeout, deadline).await?
+                                        }
+                                    },
+                                    Err(rejection) => rejection,
+                                };
                         }
 
-                        let execution_result = vm_context.result.lock();
-                        next_promise_queue = next_queue.lock().clone();
-                        promise_queue_mut.queue[index].status =
-                            PromiseStatus::Fulfilled(Some(execution_result.clone()));
-                    }
+                        PromiseAction::DatabaseDelete(db_action) => {
+                            promise_queue_mut.queue[index].status =
+                                match db_namespace(&self.node_config, program_name, &db_action.namespace) {
+                                    Ok(namespace) if db_action.immediate => {
+                                        db_writes.remove(namespace, &db_action.key);
+                                        let db_delete = async {
+                                            self.host_adapter.db_delete(namespace, &db_action.key).await.into()
+                                        };
+                                        with_timeout(action, db_delete, promise_timeout, deadline).await?
+                                    }
+                                    Ok(namespace) => {
+                                        db_writes.delete(namespace, &db_action.key);
+                                        PromiseStatus::Fulfilled(Some(Vec::new()))
+                                    }
+                                    Err(rejection) => rejection,
+                                };
+                        }
 
-                    // Just an example, delete this later
-                    PromiseAction::DatabaseSet(db_action) => {
-                        let res = String::from_bytes(&db_action.value);
-                        promise_queue_mut.queue[index].status = if res.is_err() {
-                            res.into()
-                        } else {
-                            self.host_adapter.db_set(&db_action.key, &res.unwrap()).await.into()
-                        };
-                    }
+                        PromiseAction::DatabaseScan(db_action) => {
+                            promise_queue_mut.queue[index].status =
+                                match db_namespace(&self.node_config, program_name, &db_action.namespace) {
+                                    Ok(namespace) => {
+                                        let db_writes = &*db_writes;
+                                        let db_scan = async {
+                                            self.host_adapter
+                                                .db_scan(namespace, &db_action.prefix)
+                                                .await
+                                                .map(|entries| {
+                                                    db_writes.overlay_scan(namespace, &db_action.prefix, entries)
+                                                })
+                                                .into()
+                                        };
+                                        with_timeout(action, db_scan, promise_timeout, deadline).await?
+                                    }
+                                    Err(rejection) => rejection,
+                                };
+                        }
 
-                    PromiseAction::DatabaseGet(db_action) => {
-                        promise_queue_mut.queue[index].status = self.host_adapter.db_get(&db_action.key).await.into();
+                        PromiseAction::Http(_) | PromiseAction::ChainView(_) => {
+                            promise_queue_mut.queue[index].status = self
+                                .execute_independent_action(action, promise_timeout, deadline)
+                                .await?;
+                        }
+                        PromiseAction::ChainCall(chain_call_action) => {
+                            let chain_call = async {
+                                self.host_adapter
+                                    .chain_call(
+                                        chain_call_action.chain,
+                                        &chain_call_action.contract_id,
+                                        &chain_call_action.method_name,
+                                        chain_call_action.args.clone(),
+                                        chain_call_action.deposit,
+                                        self.node_config.clone(),
+                                    )
+                                    .await
+                                    .into()
+                            };
+                            promise_queue_mut.queue[index].status =
+                                with_timeout(action, chain_call, promise_timeout, deadline).await?;
+                        }
+                        PromiseAction::TriggerEvent(trigger_event_action) => {
+                            let trigger_event = async {
+                                self.host_adapter
+                                    .trigger_event(trigger_event_action.event.clone())
+                                    .await
+                                    .into()
+                            };
+                            promise_queue_mut.queue[index].status =
+                                with_timeout(action, trigger_event, promise_timeout, deadline).await?;
+                        }
+                        PromiseAction::P2PBroadcast(p2p_broadcast_action) => {
+                            let envelope = P2PEnvelope {
+                                timestamp: consensus.timestamp,
+                                data:      p2p_broadcast_action.data.clone(),
+                            };
+                            let p2p_broadcast = async {
+                                match serde_json::to_vec(&envelope) {
+                                    Ok(data) => self.host_adapter.p2p_broadcast(data).await.into(),
+                                    Err(err) => Err::<(), _>(err).into(),
+                                }
+                            };
+                            promise_queue_mut.queue[index].status =
+                                with_timeout(action, p2p_broadcast, promise_timeout, deadline).await?;
+                        }
                     }
 
-                    PromiseAction::Http(http_action) => {
-                        promise_queue_mut.queue[index].status =
-                            self.host_adapter.http_fetch(&http_action.url).await.into();
-                    }
-                    PromiseAction::ChainView(chain_view_action) => {
-                        promise_queue_mut.queue[index].status = self
-                            .host_adapter
-                            .chain_view(
-                                chain_view_action.chain,
-                                &chain_view_action.contract_id,
-                                &chain_view_action.method_name,
-                                chain_view_action.args.clone(),
-                            )
-                            .await
-                            .into();
-                    }
-                    PromiseAction::ChainCall(chain_call_action) => {
-                        promise_queue_mut.queue[index].status = self
-                            .host_adapter
-                            .chain_call(
-                                chain_call_action.chain,
-                                &chain_call_action.contract_id,
-                                &chain_call_action.method_name,
-                                chain_call_action.args.clone(),
-                                chain_call_action.deposit,
-                                self.node_config.clone(),
-                            )
-                            .await
-                            .into();
-                    }
-                    PromiseAction::TriggerEvent(trigger_event_action) => {
-                        promise_queue_mut.queue[index].status = self
-                            .host_adapter
-                            .trigger_event(trigger_event_action.event.clone())
-                            .await
-                            .into();
-                    }
-                    PromiseAction::P2PBroadcast(p2p_broadcast_action) => {
-                        // TODO we need to figure out how to handle success and errors using channels.
-                        p2p_command_sender_channel
-                            .send(P2PCommand::Broadcast(p2p_broadcast_action.data.clone()))
-                            .await
-                            .expect("fixed with above TODO");
-                    }
+                    Ok(None)
+                }
+                .await;
+
+                execution_trace.record(
+                    &promise_queue_mut.queue[index],
+                    promise_started,
+                    Instant::now(),
+                    promise_stdout,
+                    promise_stderr,
+                );
+                match outcome {
+                    Ok(None) => index += 1,
+                    Ok(Some(status)) => return Ok(status),
+                    Err(status) => return Err(status),
                 }
             }
-        }
-
-        promise_queue_trace.push(promise_queue_mut.clone());
 
-        let res = self.execute_promise_queue(
-            wasm_module,
-            memory_adapter,
-            next_promise_queue,
-            stdout,
-            stderr,
-            promise_queue_trace,
-            p2p_command_sender_channel,
-        );
-
-        res.await
+            promise_queue_trace.push(promise_queue_mut);
+            promise_queue = next_promise_queue;
+        }
     }
 
-    async fn start_runtime(
-        &self,
-        config: VmConfig,
-        memory_adapter: Arc<Mutex<InMemory>>,
-        p2p_command_sender_channel: Sender<P2PCommand>,
-    ) -> VmResult {
+    async fn start_runtime(&self, config: VmConfig, memory_adapter: Arc<Mutex<InMemory>>) -> VmResult {
         let function_name = config.clone().start_func.unwrap_or_else(|| "_start".to_string());
         let wasm_module = self.wasm_module.as_ref().unwrap();
 
@@ -277,29 +568,54 @@ impl<HA: HostAdapter> RunnableRuntime for Runtime<HA> {
         let mut promise_queue = PromiseQueue::new();
 
         promise_queue.add_promise(Promise {
-            action: PromiseAction::CallSelf(CallSelfAction {
+            action:    PromiseAction::CallSelf(CallSelfAction {
                 function_name,
                 args: config.args,
+                input: Vec::new(),
             }),
-            status: PromiseStatus::Unfulfilled,
+            status:    PromiseStatus::Unfulfilled,
+            condition: PromiseCondition::Always,
         });
 
         let mut stdout: Vec<String> = vec![];
         let mut stderr: Vec<String> = vec![];
 
+        let gas_limit = config.gas_limit.unwrap_or(u64::MAX);
+        let mut remaining_gas = gas_limit;
+        let mut db_writes = DatabaseWriteBuffer::default();
+        let mut execution_trace = ExecutionTrace::new(config.debug);
+        let consensus = ConsensusContext::new(config.timestamp, config.seed);
+        let deadline = Instant::now() + Duration::from_millis(self.node_config.job_timeout_ms);
+
         let exit_info: ExitInfo = self
             .execute_promise_queue(
                 wasm_module,
+                &config.program_name,
                 memory_adapter,
                 promise_queue,
                 &mut stdout,
                 &mut stderr,
                 &mut promise_queue_trace,
-                p2p_command_sender_channel,
+                &mut execution_trace,
+                &mut remaining_gas,
+                deadline,
+                config.cancellation_token,
+                &mut db_writes,
+                &consensus,
             )
             .await
             .into();
 
+        // The writes are only committed when the whole promise queue succeeded
+        let exit_info = if exit_info.exit_code == 0 && !db_writes.is_empty() {
+            match self.host_adapter.db_commit(db_writes.into_writes()).await {
+                Ok(()) => exit_info,
+                Err(err) => VmResultStatus::DatabaseCommitFailed(err.to_string()).into(),
+            }
+        } else {
+            exit_info
+        };
+
         // There is always 1 queue with 1 promise in the trace (due to this func adding
         // the entrypoint). Only if we haven't hit exit codes, since we no longer return
         // early.
@@ -329,6 +645,56 @@ impl<HA: HostAdapter> RunnableRuntime for Runtime<HA> {
             stderr,
             result,
             exit_info,
+            gas_used: gas_limit - remaining_gas,
+            trace: execution_trace.is_enabled().then_some(execution_trace),
         }
     }
 }
+
+/// The database namespace of an action, a binary uses its own namespace
+/// unless the action asks for one the node config shares. A binary can never
+/// use the namespace of another binary.
+pub(crate) fn db_namespace<'a>(
+    node_config: &NodeConfig,
+    program_name: &'a str,
+    namespace: &'a Option<String>,
+) -> std::result::Result<&'a str, PromiseStatus> {
+    match namespace {
+        None => Ok(program_name),
+        Some(namespace) if namespace == program_name => Ok(namespace),
+        Some(namespace)
+            if node_config.shared_db_namespaces.contains(namespace)
+                && !node_config.wasm_binaries.contains_key(namespace) =>
+        {
+            Ok(namespace)
+        }
+        Some(namespace) => Err(PromiseStatus::Rejected(
+            format!("Database namespace `{namespace}` is not shared").into_bytes(),
+        )),
+    }
+}
+
+/// Awaits the result of a promise action. The promise gets rejected when it
+/// takes longer than the promise timeout, the whole execution stops when the
+/// job deadline is reached first.
+async fn with_timeout<F>(
+    action: &PromiseAction,
+    future: F,
+    promise_timeout: Duration,
+    deadline: Instant,
+) -> ExecutionResult<PromiseStatus>
+where
+    F: Future<Output = PromiseStatus>,
+{
+    let time_left = deadline.saturating_duration_since(Instant::now());
+
+    if promise_timeout < time_left {
+        Ok(timeout(promise_timeout, future).await.unwrap_or_else(|_| {
+            PromiseStatus::Rejected(
+                format!("Promise `{action}` timed out after {}ms", promise_timeout.as_millis()).into_bytes(),
+            )
+        }))
+    } else {
+        timeout(time_left, future).await.map_err(|_| VmResultStatus::Timeout)
+    }
+}
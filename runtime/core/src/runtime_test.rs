@@ -1,12 +1,36 @@
-use std::{env, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    env,
+    fs,
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
 use parking_lot::Mutex;
-use seda_config::{ChainConfigsInner, NodeConfigInner};
-use seda_runtime_sdk::p2p::P2PCommand;
+use seda_config::{Capabilities, ChainConfigs, ChainConfigsInner, NodeConfigInner, WasmBinarySource};
+use seda_runtime_sdk::{DatabaseEntry, GuestConfig, PromiseAction, PromiseStatus};
 use serde_json::json;
-use tokio::sync::mpsc;
 
-use crate::{test::RuntimeTestAdapter, HostAdapter, InMemory, MemoryAdapter, RunnableRuntime, Runtime, VmConfig};
+use crate::{
+    hash_wasm_binary,
+    test::{RuntimeTestAdapter, TestChainInteractor, TestEventSink, TestHttp, TestP2P},
+    Adapter,
+    CancellationToken,
+    DatabaseAdapter,
+    DatabaseWrite,
+    HostAdapter,
+    InMemory,
+    MemoryAdapter,
+    ModuleCache,
+    RunnableRuntime,
+    Runtime,
+    RuntimeError,
+    VmConfig,
+};
 
 fn read_wasm_target(file: &str) -> Vec<u8> {
     let mut path_prefix = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -24,84 +48,940 @@ fn memory_adapter() -> Arc<Mutex<InMemory>> {
     Arc::new(Mutex::new(InMemory::default()))
 }
 
+/// Serves every request after the delay, with the path of the request as the
+/// body. Returns the base URL of the server.
+fn delayed_http_server(delay: Duration) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            thread::spawn(move || {
+                let mut request = [0; 1024];
+                let length = stream.read(&mut request).unwrap();
+                let path = String::from_utf8_lossy(&request[..length])
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+
+                thread::sleep(delay);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{path}",
+                    path.len()
+                )
+                .unwrap();
+            });
+        }
+    });
+
+    format!("http://{address}")
+}
+
+/// A database that can't be written to, mixed with the other test backends
+struct ReadOnlyDatabase;
+
+#[async_trait::async_trait]
+impl DatabaseAdapter for ReadOnlyDatabase {
+    type Error = RuntimeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self, RuntimeError> {
+        Ok(Self)
+    }
+
+    async fn get(&self, _namespace: &str, _key: &str) -> Result<Option<Vec<u8>>, RuntimeError> {
+        Ok(None)
+    }
+
+    async fn set(&self, _namespace: &str, _key: &str, _value: &[u8]) -> Result<(), RuntimeError> {
+        Err(RuntimeError::NodeError("The database is read only".to_string()))
+    }
+
+    async fn delete(&self, _namespace: &str, _key: &str) -> Result<(), RuntimeError> {
+        Err(RuntimeError::NodeError("The database is read only".to_string()))
+    }
+
+    async fn scan(&self, _namespace: &str, _prefix: &str) -> Result<Vec<DatabaseEntry>, RuntimeError> {
+        Ok(Vec::new())
+    }
+
+    async fn commit(&self, _writes: Vec<DatabaseWrite>) -> Result<(), RuntimeError> {
+        Err(RuntimeError::NodeError("The database is read only".to_string()))
+    }
+}
+
+#[derive(Adapter)]
+#[adapter(
+    error = RuntimeError,
+    database = ReadOnlyDatabase,
+    http = TestHttp,
+    chain_interactor = TestChainInteractor,
+    event_sink = TestEventSink,
+    p2p = TestP2P
+)]
+struct ReadOnlyAdapter {
+    database:         ReadOnlyDatabase,
+    http:             TestHttp,
+    chain_interactor: TestChainInteractor,
+    event_sink:       TestEventSink,
+    p2p:              TestP2P,
+}
+
+/// A database whose reads never answer, mixed with the other test backends
+struct HangingDatabase;
+
+#[async_trait::async_trait]
+impl DatabaseAdapter for HangingDatabase {
+    type Error = RuntimeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self, RuntimeError> {
+        Ok(Self)
+    }
+
+    async fn get(&self, _namespace: &str, _key: &str) -> Result<Option<Vec<u8>>, RuntimeError> {
+        futures::future::pending().await
+    }
+
+    async fn set(&self, _namespace: &str, _key: &str, _value: &[u8]) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn delete(&self, _namespace: &str, _key: &str) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn scan(&self, _namespace: &str, _prefix: &str) -> Result<Vec<DatabaseEntry>, RuntimeError> {
+        Ok(Vec::new())
+    }
+
+    async fn commit(&self, _writes: Vec<DatabaseWrite>) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+}
+
+#[derive(Adapter)]
+#[adapter(
+    error = RuntimeError,
+    database = HangingDatabase,
+    http = TestHttp,
+    chain_interactor = TestChainInteractor,
+    event_sink = TestEventSink,
+    p2p = TestP2P
+)]
+struct HangingDatabaseAdapter {
+    database:         HangingDatabase,
+    http:             TestHttp,
+    chain_interactor: TestChainInteractor,
+    event_sink:       TestEventSink,
+    p2p:              TestP2P,
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_multiple_calls_with_external_traits() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime.start_runtime(
+        VmConfig {
+            args:               vec!["hello world".to_string()],
+            program_name:       "consensus".to_string(),
+            start_func:         None,
+            debug:              true,
+            gas_limit:          None,
+            cancellation_token: CancellationToken::default(),
+            timestamp:          0,
+            seed:               [0; 32],
+        },
+        memory_adapter,
+    );
+
+    let vm_result = runtime_execution_result.await;
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    let value = runtime.host_adapter.db_get("consensus", "test_value").await.unwrap();
+
+    assert!(value.is_some());
+    assert_eq!(value.unwrap(), b"completed");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[should_panic(expected = "Unexpected EOF")]
+async fn test_bad_wasm_file() {
+    set_env_vars();
+
+    let node_config = NodeConfigInner::test_config();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+
+    runtime.init(vec![203]).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_non_existing_function() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec!["hello world".to_string()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("non_existing_function".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_out_of_gas() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("infinite_loop_test".to_string()),
+                debug:              true,
+                gas_limit:          Some(1_000_000),
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 9);
+    assert_eq!(runtime_execution_result.gas_used, 1_000_000);
+
+    // The promise that ran out of gas is the last one of the trace
+    let trace = runtime_execution_result.trace.unwrap();
+    let failed_promise = trace.queues.last().unwrap().last().unwrap();
+    assert!(matches!(failed_promise.action, PromiseAction::CallSelf(_)));
+    assert!(matches!(failed_promise.status, PromiseStatus::Pending));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_job_timeout_interrupts_call() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        job_timeout_ms: 100,
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("interruptible_loop_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 10);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancel_interrupts_call() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    // Cancels once the call runs, so it gets interrupted instead of never starting
+    let memory_adapter = memory_adapter();
+    let running = memory_adapter.clone();
+    let cancellation_token = CancellationToken::default();
+    let canceller = cancellation_token.clone();
+    thread::spawn(move || {
+        while running.lock().get::<Vec<u8>>("running").unwrap().is_none() {
+            thread::yield_now();
+        }
+        canceller.cancel();
+    });
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args: vec![],
+                program_name: "consensus".to_string(),
+                start_func: Some("interruptible_loop_test".to_string()),
+                debug: true,
+                gas_limit: None,
+                cancellation_token,
+                timestamp: 0,
+                seed: [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 11);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_timeout_rejects_promise() {
+    set_env_vars();
+    // Accepts connections in its backlog but never responds
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let fetch_url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        promise_timeout_ms: 100,
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![fetch_url],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("http_fetch_rejected_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    assert!(vm_result
+        .stdout
+        .into_iter()
+        .any(|output| output.contains("Promise `http` timed out after 100ms")));
+    drop(listener);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_memory_limit_exceeded() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("memory_limit_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 12);
+}
+
+#[test]
+fn test_module_cache() {
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let cache_dir = env::temp_dir().join("seda_test_module_cache");
+    let _ = fs::remove_dir_all(&cache_dir);
+
+    let module_cache = ModuleCache::new(NodeConfigInner::MAX_MEMORY_PAGES, Some(cache_dir.clone()));
+    let module = module_cache.get_or_compile(&wasm_binary).unwrap();
+    assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+
+    // A fresh cache loads the module from disk instead of compiling it
+    let disk_cache = ModuleCache::new(NodeConfigInner::MAX_MEMORY_PAGES, Some(cache_dir));
+    let cached_module = disk_cache.get_or_compile(&wasm_binary).unwrap();
+    assert_eq!(module.exports().count(), cached_module.exports().count());
+}
+
+#[test]
+fn test_module_cache_load() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../target/wasm32-wasi/debug/promise-wasm-bin.wasm");
+    let module_cache = ModuleCache::new(NodeConfigInner::MAX_MEMORY_PAGES, None);
+
+    let hash = module_cache.load(&WasmBinarySource::Path(path)).unwrap();
+    assert_eq!(hash, hash_wasm_binary(&read_wasm_target("promise-wasm-bin")));
+    assert!(module_cache.load(&WasmBinarySource::Hash(hash)).is_ok());
+
+    let unknown_hash = hash_wasm_binary(b"not cached");
+    assert!(matches!(
+        module_cache.load(&WasmBinarySource::Hash(unknown_hash)),
+        Err(RuntimeError::ModuleNotCached(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_http_fetch() {
+    set_env_vars();
+    let fetch_url = "https://swapi.dev/api/planets/1/".to_string();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![fetch_url.clone()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("http_fetch_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
+
+    let db_result = runtime
+        .host_adapter
+        .db_get("consensus", "http_fetch_result")
+        .await
+        .unwrap();
+
+    assert!(db_result.is_some());
+
+    let result = String::from_utf8(db_result.unwrap()).unwrap();
+    // Compare result with real API fetch
+    let expected_result = reqwest::get(fetch_url).await.unwrap().text().await.unwrap();
+
+    println!("Decoded result {}", result);
+    assert_eq!(result, expected_result);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_http_post() {
+    set_env_vars();
+    let post_url = "https://httpbin.org/post".to_string();
+    let post_body = r#"{"seda":"post"}"#.to_string();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![post_url, post_body.clone()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("http_post_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
+
+    let status = runtime
+        .host_adapter
+        .db_get("consensus", "http_post_status")
+        .await
+        .unwrap();
+    assert_eq!(status, Some(b"200".to_vec()));
+
+    // httpbin echoes the posted body back in the `data` field
+    let result = runtime
+        .host_adapter
+        .db_get("consensus", "http_post_result")
+        .await
+        .unwrap()
+        .unwrap();
+    let result: serde_json::Value = serde_json::from_slice(&result).unwrap();
+    assert_eq!(result["data"], post_body);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_db_delete_and_scan() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("db_scan_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
+
+    let deleted = runtime.host_adapter.db_get("consensus", "scan_b").await.unwrap();
+    assert!(deleted.is_none());
+
+    let result = runtime
+        .host_adapter
+        .db_get("consensus", "db_scan_result")
+        .await
+        .unwrap();
+    assert_eq!(result, Some(b"scan_a".to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_trace() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_config = VmConfig {
+        args:               vec![],
+        program_name:       "consensus".to_string(),
+        start_func:         Some("db_scan_test".to_string()),
+        debug:              true,
+        gas_limit:          None,
+        cancellation_token: CancellationToken::default(),
+        timestamp:          0,
+        seed:               [0; 32],
+    };
+
+    let vm_result = runtime.start_runtime(vm_config.clone(), memory_adapter()).await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+
+    let trace = vm_result.trace.unwrap();
+    assert_eq!(trace.queues.len(), 4);
+    assert_eq!(trace.queues[1].len(), 6);
+    assert!(matches!(trace.queues[1][4].action, PromiseAction::DatabaseScan(_)));
+    assert!(matches!(trace.queues[1][4].status, PromiseStatus::Fulfilled(_)));
+    assert!(matches!(trace.queues[2][0].action, PromiseAction::CallSelf(_)));
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                debug: false,
+                ..vm_config
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    assert!(vm_result.trace.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_db_namespaces() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        shared_db_namespaces: vec!["shared".to_string()],
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let runtime_execution_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "data-request".to_string(),
+                start_func:         Some("db_namespace_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
+
+    let own = runtime
+        .host_adapter
+        .db_get("data-request", "namespace_own")
+        .await
+        .unwrap();
+    assert_eq!(own, Some(b"own".to_vec()));
+    let other = runtime.host_adapter.db_get("consensus", "namespace_own").await.unwrap();
+    assert!(other.is_none());
+
+    let shared = runtime.host_adapter.db_get("shared", "namespace_shared").await.unwrap();
+    assert_eq!(shared, Some(b"shared".to_vec()));
+
+    let not_shared = runtime
+        .host_adapter
+        .db_get("not_shared", "namespace_not_shared")
+        .await
+        .unwrap();
+    assert!(not_shared.is_none());
+    let rejected = runtime
+        .host_adapter
+        .db_get("data-request", "namespace_rejected")
+        .await
+        .unwrap();
+    assert_eq!(rejected, Some(b"true".to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_catch() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("promise_catch_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+
+    let trace = vm_result.trace.unwrap();
+    assert!(matches!(trace.queues[1][1].status, PromiseStatus::Rejected(_)));
+
+    let skipped = runtime.host_adapter.db_get("consensus", "catch_skipped").await.unwrap();
+    assert!(skipped.is_none());
+
+    for key in ["catch_caught", "catch_recovered", "catch_finally"] {
+        let value = runtime.host_adapter.db_get("consensus", key).await.unwrap();
+        assert!(value.is_some(), "`{key}` should have been set");
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
-async fn test_promise_queue_multiple_calls_with_external_traits() {
+async fn test_promise_queue_db_writes_rolled_back_on_error() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
+
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
     let memory_adapter = memory_adapter();
     let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
         .await
         .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("db_transaction_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 8);
+    assert!(vm_result
+        .stdout
+        .iter()
+        .any(|output| output.contains("Read buffered value: buffered")));
+
+    let buffered = runtime
+        .host_adapter
+        .db_get("consensus", "transaction_buffered")
+        .await
+        .unwrap();
+    assert!(buffered.is_none());
 
+    let immediate = runtime
+        .host_adapter
+        .db_get("consensus", "transaction_immediate")
+        .await
+        .unwrap();
+    assert_eq!(immediate, Some(b"immediate".to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_call_input() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
     runtime.init(wasm_binary).unwrap();
 
-    let runtime_execution_result = runtime.start_runtime(
-        VmConfig {
-            args:         vec!["hello world".to_string()],
-            program_name: "consensus".to_string(),
-            start_func:   None,
-            debug:        true,
-        },
-        memory_adapter,
-        p2p_command_sender,
-    );
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("call_input_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
 
-    let vm_result = runtime_execution_result.await;
     assert_eq!(vm_result.exit_info.exit_code, 0);
-    let value = runtime.host_adapter.db_get("test_value").await.unwrap();
 
-    assert!(value.is_some());
-    assert_eq!(value.unwrap(), "completed");
+    let result = runtime
+        .host_adapter
+        .db_get("consensus", "call_input_result")
+        .await
+        .unwrap();
+    assert_eq!(result, Some(b"42 typed".to_vec()));
 }
 
 #[tokio::test(flavor = "multi_thread")]
-#[should_panic(expected = "Unexpected EOF")]
-async fn test_bad_wasm_file() {
+async fn test_promise_queue_callback() {
     set_env_vars();
 
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
+    let memory_adapter = memory_adapter();
     let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
         .await
         .unwrap();
+    runtime.init(wasm_binary).unwrap();
 
-    runtime.init(vec![203]).unwrap();
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("callback_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter,
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    assert_eq!(vm_result.result, Some(b"Value: 42".to_vec()));
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_non_existing_function() {
+async fn test_sync_host_imports() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
+
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("sync_host_test".to_string()),
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    assert_eq!(vm_result.result, Some(b"sync".to_vec()));
+
+    // The synchronous writes are committed with the rest of the execution
+    let value = runtime.host_adapter.db_get("consensus", "sync_value").await.unwrap();
+    assert_eq!(value, Some(b"sync".to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_db_get_stops_at_job_timeout() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        job_timeout_ms: 100,
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<HangingDatabaseAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    // Reading a key that isn't buffered waits for the database, which never answers
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("sync_host_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 10);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_promise_queue_max_generations() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        max_promise_generations: 10,
+        ..(*NodeConfigInner::test_config()).clone()
+    });
     let memory_adapter = memory_adapter();
     let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
         .await
         .unwrap();
     runtime.init(wasm_binary).unwrap();
 
-    let runtime_execution_result = runtime
+    let vm_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec!["hello world".to_string()],
-                program_name: "consensus".to_string(),
-                start_func:   Some("non_existing_function".to_string()),
-                debug:        true,
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("call_self_loop_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter,
-            p2p_command_sender,
         )
         .await;
 
-    assert_eq!(runtime_execution_result.exit_info.exit_code, 5);
+    assert_eq!(vm_result.exit_info.exit_code, 14);
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_promise_queue_http_fetch() {
+async fn test_promise_queue_http_fetch_parallel() {
     set_env_vars();
-    let fetch_url = "https://swapi.dev/api/planets/1/".to_string();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
+    let delay = Duration::from_secs(1);
+    let server_url = delayed_http_server(delay);
+    let fetch_urls = vec![format!("{server_url}/0"), format!("{server_url}/1")];
 
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
@@ -111,38 +991,44 @@ async fn test_promise_queue_http_fetch() {
         .unwrap();
     runtime.init(wasm_binary).unwrap();
 
+    let started = Instant::now();
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec![fetch_url.clone()],
-                program_name: "consensus".to_string(),
-                start_func:   Some("http_fetch_test".to_string()),
-                debug:        true,
+                args:               fetch_urls,
+                program_name:       "consensus".to_string(),
+                start_func:         Some("http_fetch_parallel_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter,
-            p2p_command_sender,
         )
         .await;
 
     assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
 
-    let db_result = runtime.host_adapter.db_get("http_fetch_result").await.unwrap();
-
-    assert!(db_result.is_some());
-
-    let result = db_result.unwrap();
-    // Compare result with real API fetch
-    let expected_result = reqwest::get(fetch_url).await.unwrap().text().await.unwrap();
-
-    println!("Decoded result {}", result);
-    assert_eq!(result, expected_result);
+    // Both requests waited for the delay at the same time
+    assert!(started.elapsed() < delay * 2);
+
+    // The results keep the order of the queue, whichever request finished first
+    for index in 0..2 {
+        let result = runtime
+            .host_adapter
+            .db_get("consensus", &format!("http_fetch_parallel_{index}"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, format!("/{index}").into_bytes());
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_memory_adapter() {
     set_env_vars();
     let node_config = NodeConfigInner::test_config();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
     let memory_adapter = memory_adapter();
     let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
         .await
@@ -153,13 +1039,16 @@ async fn test_memory_adapter() {
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec!["memory adapter".to_string()],
-                program_name: "consensus".to_string(),
-                start_func:   Some("memory_adapter_test_success".to_string()),
-                debug:        true,
+                args:               vec!["memory adapter".to_string()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("memory_adapter_test_success".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter.clone(),
-            p2p_command_sender,
         )
         .await;
 
@@ -171,22 +1060,21 @@ async fn test_memory_adapter() {
     let expected_str = format!("{expected:?}");
     assert!(read_value.is_ok());
     assert_eq!(read_value.unwrap(), Some(expected));
-    let u8_value = runtime.host_adapter.db_get("u8_result").await.unwrap();
+    let u8_value = runtime.host_adapter.db_get("consensus", "u8_result").await.unwrap();
     assert!(u8_value.is_some());
-    assert_eq!(u8_value.unwrap(), expected_str);
+    assert_eq!(u8_value.unwrap(), expected_str.as_bytes());
 
-    let u32_value = runtime.host_adapter.db_get("u32_result").await.unwrap();
+    let u32_value = runtime.host_adapter.db_get("consensus", "u32_result").await.unwrap();
     let expected = 3467u32.to_le_bytes().to_vec();
     let expected_str = format!("{expected:?}");
     assert!(u32_value.is_some());
-    assert_eq!(u32_value.unwrap(), expected_str);
+    assert_eq!(u32_value.unwrap(), expected_str.as_bytes());
 }
 
 #[tokio::test(flavor = "multi_thread")]
 #[should_panic(expected = "not implemented")]
 async fn test_cli_demo_view_another_chain() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
     let wasm_binary = read_wasm_target("demo-cli");
     let node_config = NodeConfigInner::test_config();
     let memory_adapter = memory_adapter();
@@ -202,33 +1090,39 @@ async fn test_cli_demo_view_another_chain() {
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec![
+                args:               vec![
                     "view".to_string(),
                     "another".to_string(),
                     contract_id,
                     method_name,
                     args,
                 ],
-                program_name: "consensus".to_string(),
-                start_func:   None,
-                debug:        true,
+                program_name:       "consensus".to_string(),
+                start_func:         None,
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter.clone(),
-            p2p_command_sender,
         )
         .await;
     assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
 
-    let db_result = runtime.host_adapter.db_get("chain_view_result").await.unwrap();
+    let db_result = runtime
+        .host_adapter
+        .db_get("consensus", "chain_view_result")
+        .await
+        .unwrap();
     assert!(db_result.is_some());
 
-    assert_eq!(db_result.unwrap(), "view".to_string());
+    assert_eq!(db_result.unwrap(), b"view");
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_limited_runtime() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
     let memory_adapter = memory_adapter();
@@ -240,30 +1134,184 @@ async fn test_limited_runtime() {
 
     let runtime_execution_result = runtime.start_runtime(
         VmConfig {
-            args:         vec![],
-            program_name: "consensus".to_string(),
-            start_func:   Some("test_limited_runtime".to_string()),
-            debug:        true,
+            args:               vec![],
+            program_name:       "consensus".to_string(),
+            start_func:         Some("test_limited_runtime".to_string()),
+            debug:              true,
+            gas_limit:          None,
+            cancellation_token: CancellationToken::default(),
+            timestamp:          0,
+            seed:               [0; 32],
         },
         memory_adapter,
-        p2p_command_sender,
     );
 
     let vm_result = runtime_execution_result.await;
     assert_eq!(vm_result.exit_info.exit_code, 0);
 
     assert_eq!(vm_result.stdout.len(), 1);
-    assert!(
-        vm_result
-            .stdout
-            .into_iter()
-            .any(|output| output.contains("not allowed in limited runtime"))
-    );
+    assert!(vm_result
+        .stdout
+        .into_iter()
+        .any(|output| output.contains("not allowed in limited runtime")));
 
-    let value = runtime.host_adapter.db_get("foo").await.unwrap();
+    let value = runtime.host_adapter.db_get("consensus", "foo").await.unwrap();
     assert!(value.is_none());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_limited_runtime_capabilities() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        wasm_capabilities: BTreeMap::from([(
+            "consensus".to_string(),
+            Capabilities {
+                actions: vec!["call_self".to_string(), "db_set".to_string(), "http".to_string()],
+                http_domains: vec!["example.com".to_string()],
+                ..Default::default()
+            },
+        )]),
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), true)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("capabilities_test".to_string()),
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    let result = String::from_utf8(vm_result.result.unwrap()).unwrap();
+    let rejections: Vec<&str> = result.split('\n').collect();
+    assert_eq!(rejections[0], "");
+    assert_eq!(
+        rejections[1],
+        "Method `http` not allowed in limited runtime, missing the capability for the `not-allowed.org` HTTP domain"
+    );
+    assert_eq!(
+        rejections[2],
+        "Method `db_set` not allowed in limited runtime, missing the capability for the `other_program` database \
+         namespace"
+    );
+    assert_eq!(
+        rejections[3],
+        "Method `db_get` not allowed in limited runtime, missing the capability for the `db_get` action"
+    );
+
+    let value = runtime.host_adapter.db_get("consensus", "capabilities").await.unwrap();
+    assert_eq!(value, Some(b"allowed".to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deterministic_host_imports() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let mut runtime =
+        Runtime::<RuntimeTestAdapter>::new(NodeConfigInner::test_config(), ChainConfigsInner::test_config(), true)
+            .await
+            .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_config = |seed| VmConfig {
+        args: vec![],
+        program_name: "consensus".to_string(),
+        start_func: Some("deterministic_host_test".to_string()),
+        debug: false,
+        gas_limit: None,
+        cancellation_token: CancellationToken::default(),
+        timestamp: 1_670_000_000_000,
+        seed,
+    };
+
+    let first = runtime.start_runtime(vm_config([1; 32]), memory_adapter()).await;
+    let second = runtime.start_runtime(vm_config([1; 32]), memory_adapter()).await;
+    let other_seed = runtime.start_runtime(vm_config([2; 32]), memory_adapter()).await;
+
+    assert_eq!(first.exit_info.exit_code, 0);
+    let result = first.result.unwrap();
+    assert_eq!(result[..8], 1_670_000_000_000u64.to_le_bytes());
+    assert_eq!(second.result.unwrap(), result);
+    assert_ne!(other_seed.result.unwrap(), result);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_crypto_host_imports() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let mut runtime =
+        Runtime::<RuntimeTestAdapter>::new(NodeConfigInner::test_config(), ChainConfigsInner::test_config(), false)
+            .await
+            .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let message = "hello world";
+    let message_hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    // Signs the SHA-256 hash of the message with a known secp256k1 key
+    let secp = secp256k1::Secp256k1::new();
+    let secp_secret_key = secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+    let secp_public_key = secp256k1::PublicKey::from_secret_key(&secp, &secp_secret_key);
+    let secp_message = secp256k1::Message::from_slice(&hex::decode(message_hash).unwrap()).unwrap();
+    let (recovery_id, secp_signature) = secp
+        .sign_ecdsa_recoverable(&secp_message, &secp_secret_key)
+        .serialize_compact();
+
+    let ed_secret_key = ed25519_dalek::SecretKey::from_bytes(&[7; 32]).unwrap();
+    let ed_public_key = ed25519_dalek::PublicKey::from(&ed_secret_key);
+    let ed_signature = ed25519_dalek::ExpandedSecretKey::from(&ed_secret_key).sign(message.as_bytes(), &ed_public_key);
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec![
+                    message.to_string(),
+                    hex::encode(secp_signature),
+                    recovery_id.to_i32().to_string(),
+                    hex::encode(secp_public_key.serialize()),
+                    hex::encode(ed_signature.to_bytes()),
+                    hex::encode(ed_public_key.to_bytes()),
+                ],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("crypto_test".to_string()),
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    let result = String::from_utf8(vm_result.result.unwrap()).unwrap();
+    let result: Vec<&str> = result.split(',').collect();
+    assert_eq!(result[0], message_hash);
+    assert_eq!(
+        result[1],
+        "47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad"
+    );
+    assert_eq!(result[2], "true");
+    assert_eq!(result[3], hex::encode(secp_public_key.serialize()));
+    assert_eq!(result[4], "true");
+    assert_eq!(result[5], "false");
+}
+
 // TODO: test with local deployment or mocked RPC
 // #[tokio::test(flavor = "multi_thread")]
 // async fn test_cli_demo_view_near_chain() {
@@ -303,7 +1351,6 @@ async fn test_limited_runtime() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_bn254_verify_valid() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
 
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
@@ -316,7 +1363,7 @@ async fn test_bn254_verify_valid() {
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec![
+                args:               vec![
                     // Message ("sample" in ASCII)
                     "73616d706c65".to_string(),
                     // Signature (compressed G1 point)
@@ -324,21 +1371,28 @@ async fn test_bn254_verify_valid() {
                     // Public Key (compressed G2 point)
                     "0b0087beab84f1aeacf30597cda920c6772ecd26ba95d84f66750a16dc9b68cea6d89173eff7f72817e4698f93fcb5a5b04b272a7085d8a12fceb5481e651df7a7".to_string()
                 ],
-                program_name: "consensus".to_string(),
-                start_func:   Some("bn254_verify_test".to_string()),
-                debug:        true,
+                program_name:       "consensus".to_string(),
+                start_func:         Some("bn254_verify_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter,
-            p2p_command_sender,
         )
         .await;
 
     assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
 
     // Fetch bn254 verify result from DB
-    let db_result = runtime.host_adapter.db_get("bn254_verify_result").await.unwrap();
+    let db_result = runtime
+        .host_adapter
+        .db_get("consensus", "bn254_verify_result")
+        .await
+        .unwrap();
     assert!(db_result.is_some());
-    let result = db_result.unwrap();
+    let result = String::from_utf8(db_result.unwrap()).unwrap();
 
     // Valid verification returns true
     assert_eq!(result, format!("{}", true));
@@ -347,7 +1401,6 @@ async fn test_bn254_verify_valid() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_bn254_verify_invalid() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
 
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
@@ -360,7 +1413,7 @@ async fn test_bn254_verify_invalid() {
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec![
+                args:               vec![
                     // Message ("sample" in ASCII)
                     "73616d706c65".to_string(),
                     // WRONG Signature (compressed G1 point) -> 1 flipped bit!
@@ -368,21 +1421,28 @@ async fn test_bn254_verify_invalid() {
                     // Public Key (compressed G2 point)
                     "0b0087beab84f1aeacf30597cda920c6772ecd26ba95d84f66750a16dc9b68cea6d89173eff7f72817e4698f93fcb5a5b04b272a7085d8a12fceb5481e651df7a7".to_string()
                 ],
-                program_name: "consensus".to_string(),
-                start_func:   Some("bn254_verify_test".to_string()),
-                debug:        true,
+                program_name:       "consensus".to_string(),
+                start_func:         Some("bn254_verify_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter,
-            p2p_command_sender,
         )
         .await;
 
     assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
 
     // Fetch bn254 verify result from DB
-    let db_result = runtime.host_adapter.db_get("bn254_verify_result").await.unwrap();
+    let db_result = runtime
+        .host_adapter
+        .db_get("consensus", "bn254_verify_result")
+        .await
+        .unwrap();
     assert!(db_result.is_some());
-    let result = db_result.unwrap();
+    let result = String::from_utf8(db_result.unwrap()).unwrap();
 
     // Valid verification returns true
     assert_eq!(result, format!("{}", false));
@@ -391,7 +1451,6 @@ async fn test_bn254_verify_invalid() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_bn254_signature() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
 
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
@@ -404,37 +1463,185 @@ async fn test_bn254_signature() {
     let runtime_execution_result = runtime
         .start_runtime(
             VmConfig {
-                args:         vec![
+                args:               vec![
                     // Message ("sample" in ASCII)
                     "73616d706c65".to_string(),
                     // Private Key
                     "2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c".to_string(),
                 ],
-                program_name: "consensus".to_string(),
-                start_func:   Some("bn254_sign_test".to_string()),
-                debug:        true,
+                program_name:       "consensus".to_string(),
+                start_func:         Some("bn254_sign_test".to_string()),
+                debug:              true,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
             },
             memory_adapter,
-            p2p_command_sender,
         )
         .await;
 
     assert_eq!(runtime_execution_result.exit_info.exit_code, 0);
 
     // Fetch bn254 sign result from DB
-    let db_result = runtime.host_adapter.db_get("bn254_sign_result").await.unwrap();
+    let db_result = runtime
+        .host_adapter
+        .db_get("consensus", "bn254_sign_result")
+        .await
+        .unwrap();
     assert!(db_result.is_some());
-    let result = db_result.unwrap();
+    let result = String::from_utf8(db_result.unwrap()).unwrap();
 
     // Check if expected signature
     let expected_signature = "020f047a153e94b5f109e4013d1bd078112817cf0d58cdf6ba8891f9849852ba5b";
     assert_eq!(result, format!("{}", expected_signature));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_guest_config() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        contract_account_id: "mc.seda.test".to_string(),
+        seda_chain_secret_key: "chain_secret".to_string(),
+        seda_secret_key: "node_secret".to_string(),
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), true)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec!["mc.seda.test".to_string()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("guest_config_test".to_string()),
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+
+    // The env of the instance holds the guest config, without the secrets
+    let result = String::from_utf8(vm_result.result.unwrap()).unwrap();
+    assert!(!result.contains("secret"));
+    let guest_config: GuestConfig = serde_json::from_str(&result).unwrap();
+    assert_eq!(guest_config.contract_account_id, "mc.seda.test");
+    assert_eq!(guest_config.chains.len(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_node_sign() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = Arc::new(NodeConfigInner {
+        seda_secret_key: "2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c".to_string(),
+        ..(*NodeConfigInner::test_config()).clone()
+    });
+    let mut runtime = Runtime::<RuntimeTestAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                // Message ("sample" in ASCII)
+                args:               vec!["73616d706c65".to_string()],
+                program_name:       "consensus".to_string(),
+                start_func:         Some("node_sign_test".to_string()),
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+    // The same signature `bn254_sign` makes with the key
+    let expected_signature = "020f047a153e94b5f109e4013d1bd078112817cf0d58cdf6ba8891f9849852ba5b";
+    assert_eq!(vm_result.result, Some(expected_signature.as_bytes().to_vec()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bn254_aggregate_signatures() {
+    set_env_vars();
+
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let mut runtime =
+        Runtime::<RuntimeTestAdapter>::new(NodeConfigInner::test_config(), ChainConfigsInner::test_config(), false)
+            .await
+            .unwrap();
+    runtime.init(wasm_binary).unwrap();
+
+    // Message ("sample" in ASCII)
+    let message = "73616d706c65";
+    let private_keys = [
+        "1ab1126ff2e37c6e6eddea943ccb3a48f83b380b856424ee552e113595525565",
+        "2009da7287c158b126123c113d1c85241b6e3294dd75c643588630a8bc0f934c",
+    ];
+
+    let mut args = vec![message.to_string()];
+    args.extend(private_keys.iter().map(|private_key| private_key.to_string()));
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args,
+                program_name: "consensus".to_string(),
+                start_func: Some("bn254_aggregate_test".to_string()),
+                debug: false,
+                gas_limit: None,
+                cancellation_token: CancellationToken::default(),
+                timestamp: 0,
+                seed: [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    assert_eq!(vm_result.exit_info.exit_code, 0);
+
+    // The aggregates match the ones computed outside of the VM
+    let private_keys: Vec<bn254::PrivateKey> = private_keys
+        .iter()
+        .map(|private_key| bn254::PrivateKey::try_from(hex::decode(private_key).unwrap().as_ref()).unwrap())
+        .collect();
+    let expected_signature = private_keys
+        .iter()
+        .map(|private_key| bn254::ECDSA::sign(hex::decode(message).unwrap(), private_key).unwrap())
+        .reduce(|aggregate, signature| aggregate + signature)
+        .unwrap();
+    let expected_public_key = private_keys
+        .iter()
+        .map(bn254::PublicKey::from_private_key)
+        .reduce(|aggregate, public_key| aggregate + public_key)
+        .unwrap();
+
+    let result = String::from_utf8(vm_result.result.unwrap()).unwrap();
+    let result: Vec<&str> = result.split(',').collect();
+    assert_eq!(result[0], hex::encode(expected_signature.to_compressed().unwrap()));
+    assert_eq!(result[1], hex::encode(expected_public_key.to_compressed().unwrap()));
+    assert_eq!(result[2], "true");
+    // The aggregate doesn't verify without all the keys of the committee
+    assert_eq!(result[3], "false");
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_error_turns_into_rejection() {
     set_env_vars();
-    let (p2p_command_sender, _p2p_command_receiver) = mpsc::channel::<P2PCommand>(100);
     let wasm_binary = read_wasm_target("promise-wasm-bin");
     let node_config = NodeConfigInner::test_config();
     let memory_adapter = memory_adapter();
@@ -446,26 +1653,59 @@ async fn test_error_turns_into_rejection() {
 
     let runtime_execution_result = runtime.start_runtime(
         VmConfig {
-            args:         vec![],
-            program_name: "consensus".to_string(),
-            start_func:   Some("test_error_turns_into_rejection".to_string()),
-            debug:        true,
+            args:               vec![],
+            program_name:       "consensus".to_string(),
+            start_func:         Some("test_error_turns_into_rejection".to_string()),
+            debug:              true,
+            gas_limit:          None,
+            cancellation_token: CancellationToken::default(),
+            timestamp:          0,
+            seed:               [0; 32],
         },
         memory_adapter,
-        p2p_command_sender,
     );
 
     let vm_result = runtime_execution_result.await;
     assert_eq!(vm_result.exit_info.exit_code, 0);
 
     assert_eq!(vm_result.stdout.len(), 1);
-    assert!(
-        vm_result
-            .stdout
-            .into_iter()
-            .any(|output| output.contains("relative URL without a base"))
-    );
+    assert!(vm_result
+        .stdout
+        .into_iter()
+        .any(|output| output.contains("relative URL without a base")));
 
-    let value = runtime.host_adapter.db_get("foo").await.unwrap();
+    let value = runtime.host_adapter.db_get("consensus", "foo").await.unwrap();
     assert!(value.is_none());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_mixed_adapter_backends() {
+    set_env_vars();
+    let wasm_binary = read_wasm_target("promise-wasm-bin");
+    let node_config = NodeConfigInner::test_config();
+    let mut runtime = Runtime::<ReadOnlyAdapter>::new(node_config, ChainConfigsInner::test_config(), false)
+        .await
+        .unwrap();
+
+    runtime.init(wasm_binary).unwrap();
+
+    let vm_result = runtime
+        .start_runtime(
+            VmConfig {
+                args:               vec!["hello world".to_string()],
+                program_name:       "consensus".to_string(),
+                start_func:         None,
+                debug:              false,
+                gas_limit:          None,
+                cancellation_token: CancellationToken::default(),
+                timestamp:          0,
+                seed:               [0; 32],
+            },
+            memory_adapter(),
+        )
+        .await;
+
+    // The writes of the program only fail once they are committed
+    assert_eq!(vm_result.exit_info.exit_code, 13);
+    assert!(vm_result.exit_info.exit_message.contains("The database is read only"));
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use seda_runtime_sdk::DatabaseEntry;
+
+/// A database write of a promise queue execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatabaseWrite {
+    pub namespace: String,
+    pub key:       String,
+    /// `None` deletes the key
+    pub value:     Option<Vec<u8>>,
+}
+
+/// Holds the database writes of a promise queue execution until it succeeds,
+/// so a failing execution doesn't leave half of its writes behind. Reads of
+/// the execution see its own writes.
+#[derive(Default)]
+pub struct DatabaseWriteBuffer {
+    writes: BTreeMap<(String, String), Option<Vec<u8>>>,
+}
+
+impl DatabaseWriteBuffer {
+    pub fn set(&mut self, namespace: &str, key: &str, value: Vec<u8>) {
+        self.writes
+            .insert((namespace.to_string(), key.to_string()), Some(value));
+    }
+
+    pub fn delete(&mut self, namespace: &str, key: &str) {
+        self.writes.insert((namespace.to_string(), key.to_string()), None);
+    }
+
+    /// Drops the buffered write of the key, used when the key is written
+    /// right away so the older buffered value doesn't overwrite it.
+    pub fn remove(&mut self, namespace: &str, key: &str) {
+        self.writes.remove(&(namespace.to_string(), key.to_string()));
+    }
+
+    /// Returns the buffered value of the key, `Some(None)` when the key was
+    /// deleted and `None` when the execution didn't write the key.
+    pub fn get(&self, namespace: &str, key: &str) -> Option<Option<Vec<u8>>> {
+        self.writes.get(&(namespace.to_string(), key.to_string())).cloned()
+    }
+
+    /// Applies the buffered writes of the namespace to the entries of a prefix
+    /// scan of the database.
+    pub fn overlay_scan(&self, namespace: &str, prefix: &str, entries: Vec<DatabaseEntry>) -> Vec<DatabaseEntry> {
+        let mut entries: BTreeMap<String, Vec<u8>> =
+            entries.into_iter().map(|entry| (entry.key, entry.value)).collect();
+
+        let buffered = self
+            .writes
+            .range((namespace.to_string(), prefix.to_string())..)
+            .take_while(|((write_namespace, key), _)| write_namespace == namespace && key.starts_with(prefix));
+        for ((_, key), value) in buffered {
+            match value {
+                Some(value) => entries.insert(key.clone(), value.clone()),
+                None => entries.remove(key),
+            };
+        }
+
+        entries
+            .into_iter()
+            .map(|(key, value)| DatabaseEntry { key, value })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    pub fn into_writes(self) -> Vec<DatabaseWrite> {
+        self.writes
+            .into_iter()
+            .map(|((namespace, key), value)| DatabaseWrite { namespace, key, value })
+            .collect()
+    }
+}
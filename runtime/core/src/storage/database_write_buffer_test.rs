@@ -0,0 +1,70 @@
+use seda_runtime_sdk::DatabaseEntry;
+
+use super::{DatabaseWrite, DatabaseWriteBuffer};
+
+fn entry(key: &str, value: &[u8]) -> DatabaseEntry {
+    DatabaseEntry {
+        key:   key.to_string(),
+        value: value.to_vec(),
+    }
+}
+
+#[test]
+fn test_database_write_buffer_get() {
+    let mut buffer = DatabaseWriteBuffer::default();
+
+    buffer.set("consensus", "set", b"value".to_vec());
+    buffer.delete("consensus", "deleted");
+
+    assert_eq!(buffer.get("consensus", "set"), Some(Some(b"value".to_vec())));
+    assert_eq!(buffer.get("consensus", "deleted"), Some(None));
+    assert_eq!(buffer.get("consensus", "untouched"), None);
+    assert_eq!(buffer.get("other", "set"), None);
+}
+
+#[test]
+fn test_database_write_buffer_overlay_scan() {
+    let mut buffer = DatabaseWriteBuffer::default();
+
+    buffer.set("consensus", "dr:1", b"new".to_vec());
+    buffer.set("consensus", "dr:3", b"added".to_vec());
+    buffer.delete("consensus", "dr:2");
+    buffer.set("consensus", "block:1", b"other prefix".to_vec());
+    buffer.set("other", "dr:4", b"other namespace".to_vec());
+
+    let entries = buffer.overlay_scan(
+        "consensus",
+        "dr:",
+        vec![entry("dr:1", b"old"), entry("dr:2", b"deleted")],
+    );
+
+    assert_eq!(entries, vec![entry("dr:1", b"new"), entry("dr:3", b"added")]);
+}
+
+#[test]
+fn test_database_write_buffer_into_writes() {
+    let mut buffer = DatabaseWriteBuffer::default();
+    assert!(buffer.is_empty());
+
+    buffer.set("consensus", "key", b"first".to_vec());
+    buffer.set("consensus", "key", b"second".to_vec());
+    buffer.delete("consensus", "deleted");
+    buffer.set("consensus", "immediate", b"value".to_vec());
+    buffer.remove("consensus", "immediate");
+
+    assert_eq!(
+        buffer.into_writes(),
+        vec![
+            DatabaseWrite {
+                namespace: "consensus".to_string(),
+                key:       "deleted".to_string(),
+                value:     None,
+            },
+            DatabaseWrite {
+                namespace: "consensus".to_string(),
+                key:       "key".to_string(),
+                value:     Some(b"second".to_vec()),
+            },
+        ]
+    );
+}
@@ -1,3 +1,6 @@
+mod database_write_buffer;
+pub use database_write_buffer::*;
+
 mod in_memory_adapter;
 pub use in_memory_adapter::*;
 
@@ -10,5 +13,6 @@ pub(crate) use seda_runtime_sdk::{Bytes, FromBytes, ToBytes};
 pub mod test {
     use super::*;
 
+    mod database_write_buffer_test;
     mod in_memory_adapter_test;
 }
@@ -1,66 +1,168 @@
-use std::collections::HashMap;
+use std::{collections::BTreeMap, time::Duration};
 
 use futures::lock::Mutex;
 use lazy_static::lazy_static;
 use seda_chains::{chain, AnotherChain, ChainAdapterTrait, Client, NearChain};
 use seda_config::{ChainConfigs, NodeConfig};
-use seda_runtime_sdk::{events::Event, Chain};
+use seda_runtime_sdk::{events::Event, Chain, DatabaseEntry, HttpAction, HttpResponse};
 
-use crate::{HostAdapter, Result, RuntimeError};
+use crate::{
+    Adapter,
+    ChainInteractorAdapter,
+    DatabaseAdapter,
+    DatabaseWrite,
+    EventSinkAdapter,
+    HttpAdapter,
+    P2PAdapter,
+    Result,
+    RuntimeError,
+};
 
 lazy_static! {
     #[derive(Clone, Default)]
-    static ref HASHMAP: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+    static ref HASHMAP: Mutex<BTreeMap<(String, String), Vec<u8>>> = Mutex::new(BTreeMap::new());
 
 }
 
+#[derive(Adapter)]
+#[adapter(
+    error = RuntimeError,
+    database = TestDatabase,
+    http = TestHttp,
+    chain_interactor = TestChainInteractor,
+    event_sink = TestEventSink,
+    p2p = TestP2P
+)]
 pub struct RuntimeTestAdapter {
-    pub another_client: Client,
-    pub near_client:    Client,
-    pub chain_configs:  ChainConfigs,
+    pub database:         TestDatabase,
+    pub http:             TestHttp,
+    pub chain_interactor: TestChainInteractor,
+    pub event_sink:       TestEventSink,
+    pub p2p:              TestP2P,
 }
 
+/// An in memory database shared by all the test adapters.
+pub struct TestDatabase;
+
 #[async_trait::async_trait]
-impl HostAdapter for RuntimeTestAdapter {
+impl DatabaseAdapter for TestDatabase {
     type Error = RuntimeError;
 
-    async fn new(config: ChainConfigs) -> Result<Self> {
-        Ok(Self {
-            another_client: Client::Another(AnotherChain::new_client(&config.another)?),
-            near_client:    Client::Near(NearChain::new_client(&config.near)?),
-            chain_configs:  config,
-        })
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
     }
 
-    fn select_client_from_chain(&self, chain: Chain) -> Client {
-        match chain {
-            Chain::Another => self.another_client.clone(),
-            Chain::Near => self.near_client.clone(),
-        }
+    async fn get(&self, namespace: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let db = HASHMAP.lock().await;
+        let value = db.get(&(namespace.to_string(), key.to_string()));
+        Ok(value.cloned())
     }
 
-    async fn db_get(&self, key: &str) -> Result<Option<String>> {
+    async fn set(&self, namespace: &str, key: &str, value: &[u8]) -> Result<()> {
+        let mut db = HASHMAP.lock().await;
+        db.insert((namespace.to_string(), key.to_string()), value.to_vec());
+        Ok(())
+    }
+
+    async fn delete(&self, namespace: &str, key: &str) -> Result<()> {
+        let mut db = HASHMAP.lock().await;
+        db.remove(&(namespace.to_string(), key.to_string()));
+        Ok(())
+    }
+
+    async fn scan(&self, namespace: &str, prefix: &str) -> Result<Vec<DatabaseEntry>> {
         let db = HASHMAP.lock().await;
-        let value = db.get(key);
-        Ok(value.cloned())
+        Ok(db
+            .range((namespace.to_string(), prefix.to_string())..)
+            .take_while(|((entry_namespace, key), _)| entry_namespace == namespace && key.starts_with(prefix))
+            .map(|((_, key), value)| DatabaseEntry {
+                key:   key.clone(),
+                value: value.clone(),
+            })
+            .collect())
     }
 
-    async fn db_set(&self, key: &str, value: &str) -> Result<()> {
+    async fn commit(&self, writes: Vec<DatabaseWrite>) -> Result<()> {
         let mut db = HASHMAP.lock().await;
-        db.insert(key.to_string(), value.to_string());
+        for write in writes {
+            match write.value {
+                Some(value) => db.insert((write.namespace, write.key), value),
+                None => db.remove(&(write.namespace, write.key)),
+            };
+        }
         Ok(())
     }
+}
+
+pub struct TestHttp;
+
+#[async_trait::async_trait]
+impl HttpAdapter for TestHttp {
+    type Error = RuntimeError;
 
-    async fn http_fetch(&self, url: &str) -> Result<String> {
-        Ok(reqwest::get(url).await?.text().await?)
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
     }
 
-    async fn chain_view(&self, chain: Chain, contract_id: &str, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>> {
+    async fn fetch(&self, action: HttpAction) -> Result<HttpResponse> {
+        let method = reqwest::Method::from_bytes(action.method.to_string().as_bytes()).expect("Always a valid method");
+        let mut request = reqwest::Client::new().request(method, &action.url);
+        for (name, value) in &action.headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = action.body {
+            request = request.body(body);
+        }
+        if let Some(timeout_ms) = action.timeout_ms {
+            request = request.timeout(Duration::from_millis(timeout_ms));
+        }
+
+        let response = request.send().await?;
+        Ok(HttpResponse {
+            status:  response.status().as_u16(),
+            headers: response
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
+                .collect(),
+            body:    response.bytes().await?.to_vec(),
+        })
+    }
+}
+
+pub struct TestChainInteractor {
+    pub another_client: Client,
+    pub near_client:    Client,
+    pub chain_configs:  ChainConfigs,
+}
+
+impl TestChainInteractor {
+    fn select_client_from_chain(&self, chain: Chain) -> Client {
+        match chain {
+            Chain::Another => self.another_client.clone(),
+            Chain::Near => self.near_client.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainInteractorAdapter for TestChainInteractor {
+    type Error = RuntimeError;
+
+    async fn new(config: &ChainConfigs) -> Result<Self> {
+        Ok(Self {
+            another_client: Client::Another(AnotherChain::new_client(&config.another)?),
+            near_client:    Client::Near(NearChain::new_client(&config.near)?),
+            chain_configs:  config.clone(),
+        })
+    }
+
+    async fn view(&self, chain: Chain, contract_id: &str, method_name: &str, args: Vec<u8>) -> Result<Vec<u8>> {
         let client = self.select_client_from_chain(chain);
         Ok(chain::view(chain, client, contract_id, method_name, args).await?)
     }
 
-    async fn chain_call(
+    async fn call(
         &self,
         chain: Chain,
         contract_id: &str,
@@ -89,8 +191,36 @@ impl HostAdapter for RuntimeTestAdapter {
         let client = self.select_client_from_chain(chain);
         Ok(chain::send_tx(chain, client, &signed_txn).await?)
     }
+}
+
+/// Drops every event, the tests don't run an event queue.
+pub struct TestEventSink;
+
+#[async_trait::async_trait]
+impl EventSinkAdapter for TestEventSink {
+    type Error = RuntimeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
+    }
+
+    async fn trigger(&self, _event: Event) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Drops every broadcast, the tests don't run a P2P server.
+pub struct TestP2P;
+
+#[async_trait::async_trait]
+impl P2PAdapter for TestP2P {
+    type Error = RuntimeError;
+
+    async fn new(_config: &ChainConfigs) -> Result<Self> {
+        Ok(Self)
+    }
 
-    async fn trigger_event(&self, _event: Event) -> Result<()> {
+    async fn broadcast(&self, _data: Vec<u8>) -> Result<()> {
         Ok(())
     }
 }
@@ -0,0 +1,65 @@
+use std::time::Instant;
+
+use seda_runtime_sdk::{Promise, PromiseAction, PromiseStatus};
+use serde::{Deserialize, Serialize};
+
+/// A promise that was executed as part of a promise queue.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PromiseTrace {
+    pub action:        PromiseAction,
+    pub status:        PromiseStatus,
+    /// When the promise started, in ms since the start of the execution
+    pub started_at_ms: u64,
+    pub duration_ms:   u64,
+    /// The output of the WASM instance, only set for `CallSelf` promises
+    pub stdout:        String,
+    pub stderr:        String,
+}
+
+/// The trace of a promise queue execution, every generation of promises the
+/// program scheduled is traced as its own queue. A promise that stopped the
+/// whole execution is the last one of the trace, with the status it had when
+/// it stopped and its output.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub queues: Vec<Vec<PromiseTrace>>,
+    #[serde(skip)]
+    started:    Option<Instant>,
+}
+
+impl ExecutionTrace {
+    /// Starts the trace of an execution, nothing is recorded when `enabled` is
+    /// false.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            queues:  Vec::new(),
+            started: enabled.then(Instant::now),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Starts tracing the next generation of promises.
+    pub fn next_queue(&mut self) {
+        if self.is_enabled() {
+            self.queues.push(Vec::new());
+        }
+    }
+
+    /// Records a promise of the current generation that ran from `started`
+    /// until `finished`.
+    pub fn record(&mut self, promise: &Promise, started: Instant, finished: Instant, stdout: String, stderr: String) {
+        if let (Some(execution_started), Some(queue)) = (self.started, self.queues.last_mut()) {
+            queue.push(PromiseTrace {
+                action: promise.action.clone(),
+                status: promise.status.clone(),
+                started_at_ms: started.duration_since(execution_started).as_millis() as u64,
+                duration_ms: finished.duration_since(started).as_millis() as u64,
+                stdout,
+                stderr,
+            });
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{cell::Cell, ptr::NonNull, sync::Arc};
+
+use loupe::MemoryUsage;
+use wasmer::{
+    vm::{self, MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition},
+    BaseTunables,
+    MemoryType,
+    Pages,
+    TableType,
+    Target,
+    Tunables,
+};
+
+thread_local! {
+    /// Set as soon as an instance requested more memory than allowed. WASM
+    /// functions run synchronously on the calling thread, so stores can be
+    /// shared between the runtime workers.
+    static MEMORY_LIMIT_EXCEEDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Resets the memory limit flag of the current thread, should be called
+/// before instantiating a module.
+pub fn reset_memory_limit_exceeded() {
+    MEMORY_LIMIT_EXCEEDED.with(|exceeded| exceeded.set(false));
+}
+
+/// Whether an instance on the current thread went over the memory limit since
+/// the last reset.
+pub fn memory_limit_exceeded() -> bool {
+    MEMORY_LIMIT_EXCEEDED.with(|exceeded| exceeded.get())
+}
+
+fn set_memory_limit_exceeded() {
+    MEMORY_LIMIT_EXCEEDED.with(|exceeded| exceeded.set(true));
+}
+
+/// Tunables that cap the linear memory of every instance to a maximum number
+/// of pages, so a WASM binary cannot take all the memory of the node.
+#[derive(MemoryUsage)]
+pub struct LimitingTunables {
+    base:  BaseTunables,
+    limit: Pages,
+}
+
+impl LimitingTunables {
+    pub fn new(max_memory_pages: u32) -> Self {
+        Self {
+            base:  BaseTunables::for_target(&Target::default()),
+            limit: Pages(max_memory_pages),
+        }
+    }
+
+    /// Caps the maximum of the memory to the limit, modules usually don't
+    /// declare a maximum themselves.
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = *requested;
+        if requested.maximum.map_or(true, |maximum| maximum > self.limit) {
+            adjusted.maximum = Some(self.limit);
+        }
+        adjusted
+    }
+
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            set_memory_limit_exceeded();
+            return Err(MemoryError::Generic(format!(
+                "Minimum of {} pages exceeds the memory limit of {} pages",
+                ty.minimum.0, self.limit.0
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Tunables for LimitingTunables {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(&self.adjust_memory(memory))
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(&self, ty: &MemoryType, style: &MemoryStyle) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        let memory = self.base.create_host_memory(&adjusted, style)?;
+
+        Ok(Arc::new(LimitedMemory { inner: memory }))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        let memory = self.base.create_vm_memory(&adjusted, style, vm_definition_location)?;
+
+        Ok(Arc::new(LimitedMemory { inner: memory }))
+    }
+
+    fn create_host_table(&self, ty: &TableType, style: &TableStyle) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+/// Wraps the memory of an instance to find out whether a failed
+/// `memory.grow` was caused by the limit.
+#[derive(Debug, MemoryUsage)]
+struct LimitedMemory {
+    inner: Arc<dyn vm::Memory>,
+}
+
+impl vm::Memory for LimitedMemory {
+    fn ty(&self) -> MemoryType {
+        self.inner.ty()
+    }
+
+    fn style(&self) -> &MemoryStyle {
+        self.inner.style()
+    }
+
+    fn size(&self) -> Pages {
+        self.inner.size()
+    }
+
+    fn grow(&self, delta: Pages) -> Result<Pages, MemoryError> {
+        self.inner.grow(delta).map_err(|err| {
+            if let MemoryError::CouldNotGrow { .. } = err {
+                set_memory_limit_exceeded();
+            }
+            err
+        })
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+}
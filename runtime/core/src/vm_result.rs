@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+
+use crate::ExecutionTrace;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ExitInfo {
     pub exit_message: String,
@@ -21,6 +24,11 @@ pub struct VmResult {
     pub stderr:    Vec<String>,
     pub result:    Option<Vec<u8>>,
     pub exit_info: ExitInfo,
+    /// The amount of gas consumed by all the executed WASM instances
+    pub gas_used:  u64,
+    /// The trace of the promise queue, only set when
+    /// [VmConfig::debug](crate::VmConfig::debug) is enabled
+    pub trace:     Option<ExecutionTrace>,
 }
 
 // TODO create a readme of all these once its better established
@@ -49,6 +57,20 @@ pub enum VmResultStatus {
     FailedToConvertVMPipeToString,
     /// An execution error from the WASM Runtime
     ExecutionError(String),
+    /// When the WASM VM consumed more gas than the configured limit
+    OutOfGas,
+    /// When the execution took longer than the configured job timeout
+    Timeout,
+    /// When the execution was cancelled before it could finish
+    Cancelled,
+    /// When the WASM VM tried to use more memory than the configured limit
+    MemoryLimitExceeded,
+    /// When the database writes of a successful execution could not be
+    /// committed
+    DatabaseCommitFailed(String),
+    /// When the program kept scheduling promises for more generations than
+    /// the configured limit
+    MaxPromiseGenerationsExceeded,
 }
 
 impl From<VmResultStatus> for ExitInfo {
@@ -71,6 +93,16 @@ impl From<VmResultStatus> for ExitInfo {
                 ("Error: Failed to convert VM pipe output to String".into(), 8).into()
             }
             VmResultStatus::ExecutionError(err) => (format!("Execution Error: {err}"), 8).into(),
+            VmResultStatus::OutOfGas => ("Error: Ran out of gas".into(), 9).into(),
+            VmResultStatus::Timeout => ("Error: Execution timed out".into(), 10).into(),
+            VmResultStatus::Cancelled => ("Error: Execution was cancelled".into(), 11).into(),
+            VmResultStatus::MemoryLimitExceeded => ("Error: Exceeded the memory limit".into(), 12).into(),
+            VmResultStatus::DatabaseCommitFailed(err) => {
+                (format!("Error: Failed to commit the database writes: {err}"), 13).into()
+            }
+            VmResultStatus::MaxPromiseGenerationsExceeded => {
+                ("Error: Exceeded the maximum number of promise generations".into(), 14).into()
+            }
         }
     }
 }
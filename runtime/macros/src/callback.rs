@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn};
+
+pub fn expand(function: ItemFn) -> syn::Result<TokenStream> {
+    let signature = &function.sig;
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(syn::Error::new_spanned(asyncness, "A callback can't be async"));
+    }
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "A callback can't be generic",
+        ));
+    }
+
+    // Every parameter is read from the result of the promise at its index
+    let args = signature
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| match input {
+            FnArg::Receiver(receiver) => Err(syn::Error::new_spanned(receiver, "A callback can't take self")),
+            FnArg::Typed(pat_type) => {
+                let ty = &pat_type.ty;
+                let index = index as i32;
+
+                Ok(quote!(
+                    <#ty as ::seda_runtime_sdk::wasm::FromPromiseStatus>::from_promise_status(
+                        ::seda_runtime_sdk::wasm::Promise::result(#index),
+                    )
+                    .unwrap_or_else(|err| panic!("Failed to read the result of promise {}: {err}", #index))
+                ))
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let name = &signature.ident;
+    let inputs = &signature.inputs;
+    let output = &signature.output;
+    let block = &function.block;
+
+    Ok(quote!(
+        #(#attrs)*
+        #[no_mangle]
+        #vis fn #name() {
+            fn callback(#inputs) #output #block
+
+            ::seda_runtime_sdk::wasm::CallbackResult::into_execution_result(callback(#(#args),*));
+        }
+    ))
+}
@@ -1,23 +1,44 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse::Parse, parse_macro_input, punctuated::Punctuated, Attribute, DeriveInput, Ident, Token};
+use syn::{
+    parse::Parse,
+    parse_macro_input,
+    punctuated::Punctuated,
+    Attribute,
+    Data,
+    DataStruct,
+    DeriveInput,
+    Fields,
+    Ident,
+    ItemFn,
+    Token,
+    Type,
+};
+
+mod callback;
 
 #[derive(Clone, Default)]
 struct AdapterActions {
+    pub error:            Option<Ident>,
     pub db:               Option<Ident>,
     pub http:             Option<Ident>,
     pub chain_interactor: Option<Ident>,
+    pub event_sink:       Option<Ident>,
+    pub p2p:              Option<Ident>,
 }
 
 impl IntoIterator for AdapterActions {
-    type IntoIter = std::array::IntoIter<Self::Item, 3>;
+    type IntoIter = std::array::IntoIter<Self::Item, 6>;
     type Item = (&'static str, Option<Ident>);
 
     fn into_iter(self) -> Self::IntoIter {
         [
+            ("error", self.error),
             ("database", self.db),
             ("http", self.http),
             ("chain_interactor", self.chain_interactor),
+            ("event_sink", self.event_sink),
+            ("p2p", self.p2p),
         ]
         .into_iter()
     }
@@ -36,9 +57,12 @@ impl AdapterActions {
         };
 
         Ok(Self {
+            error:            either(self.error, other.error)?,
             db:               either(self.db, other.db)?,
             http:             either(self.http, other.http)?,
             chain_interactor: either(self.chain_interactor, other.chain_interactor)?,
+            event_sink:       either(self.event_sink, other.event_sink)?,
+            p2p:              either(self.p2p, other.p2p)?,
         })
     }
 
@@ -69,69 +93,162 @@ impl AdapterActions {
 }
 
 mod keywords {
+    syn::custom_keyword!(error);
     syn::custom_keyword!(database);
     syn::custom_keyword!(http);
     syn::custom_keyword!(chain_interactor);
+    syn::custom_keyword!(event_sink);
+    syn::custom_keyword!(p2p);
 }
 
 impl Parse for AdapterActions {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(keywords::database) {
+        let mut actions = Self::default();
+        let action = if input.peek(keywords::error) {
+            input.parse::<keywords::error>()?;
+            &mut actions.error
+        } else if input.peek(keywords::database) {
             input.parse::<keywords::database>()?;
-            input.parse::<syn::Token![=]>()?;
-            let db = input.parse::<syn::Ident>()?;
-
-            Ok(Self {
-                db:               Some(db),
-                http:             None,
-                chain_interactor: None,
-            })
+            &mut actions.db
         } else if input.peek(keywords::http) {
             input.parse::<keywords::http>()?;
-            input.parse::<syn::Token![=]>()?;
-            let http = input.parse::<syn::Ident>()?;
-            Ok(Self {
-                http:             Some(http),
-                db:               None,
-                chain_interactor: None,
-            })
+            &mut actions.http
         } else if input.peek(keywords::chain_interactor) {
             input.parse::<keywords::chain_interactor>()?;
-            input.parse::<syn::Token![=]>()?;
-            let chain_interactor = input.parse::<syn::Ident>()?;
-            Ok(Self {
-                chain_interactor: Some(chain_interactor),
-                http:             None,
-                db:               None,
-            })
+            &mut actions.chain_interactor
+        } else if input.peek(keywords::event_sink) {
+            input.parse::<keywords::event_sink>()?;
+            &mut actions.event_sink
+        } else if input.peek(keywords::p2p) {
+            input.parse::<keywords::p2p>()?;
+            &mut actions.p2p
         } else {
-            Err(syn::Error::new(
+            return Err(syn::Error::new(
                 input.span(),
                 "Unknown adapter type or empty parse stream",
-            ))
-        }
+            ));
+        };
+
+        input.parse::<syn::Token![=]>()?;
+        *action = Some(input.parse::<syn::Ident>()?);
+
+        Ok(actions)
     }
 }
 
+/// Implements `HostAdapterTypes`, and with it `HostAdapter`, for a struct
+/// that holds one field per backend type given in the `adapter` attribute.
 #[proc_macro_derive(Adapter, attributes(adapter))]
 pub fn adapter(input: TokenStream) -> TokenStream {
     let derive = parse_macro_input!(input as DeriveInput);
     let name = &derive.ident;
+    let fields = match &derive.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => fields.named.iter().collect::<Vec<_>>(),
+        _ => {
+            return syn::Error::new_spanned(name, "An adapter has to be a struct with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
     let actions = match AdapterActions::from_attrs(derive.attrs) {
         Ok(actions) => actions,
         Err(err) => return err.to_compile_error().into(),
     };
-    let db = actions.db.unwrap();
-    let http = actions.http.unwrap();
-    let chain_interactor = actions.chain_interactor.unwrap();
+    let error = actions.error.clone().unwrap();
+    let db = actions.db.clone().unwrap();
+    let http = actions.http.clone().unwrap();
+    let chain_interactor = actions.chain_interactor.clone().unwrap();
+    let event_sink = actions.event_sink.clone().unwrap();
+    let p2p = actions.p2p.clone().unwrap();
+
+    // Every backend is stored in the first field of its type that isn't taken
+    // by another backend yet
+    let mut backend_fields = Vec::new();
+    for (backend, ty) in actions.into_iter().skip(1) {
+        let ty = ty.unwrap();
+        let field = fields.iter().enumerate().find(|(index, field)| {
+            !backend_fields.iter().any(|(_, taken)| taken == index)
+                && matches!(&field.ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(&ty))
+        });
+
+        match field {
+            Some((index, _)) => backend_fields.push((Ident::new(backend, ty.span()), index)),
+            None => {
+                return syn::Error::new_spanned(&ty, format!("Missing a field of type `{ty}` for the {backend}"))
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+
+    let mut field_inits = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let field_name = &field.ident;
+        match backend_fields.iter().find(|(_, taken)| *taken == index) {
+            Some((backend, _)) => field_inits.push(quote!(#field_name: #backend)),
+            None => {
+                return syn::Error::new_spanned(field, "An adapter can only hold its backends")
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+
+    let accessors = backend_fields.iter().map(|(backend, index)| {
+        let field_name = &fields[*index].ident;
+        let ty = &fields[*index].ty;
+
+        quote!(
+            fn #backend(&self) -> &#ty {
+                &self.#field_name
+            }
+        )
+    });
 
     let adapter_trait_impl = quote!(
-        impl HostAdapterTypes for #name {
+        impl ::seda_runtime::HostAdapterTypes for #name {
+          type Error = #error;
           type Database = #db;
           type Http = #http;
           type ChainInteractor = #chain_interactor;
+          type EventSink = #event_sink;
+          type P2P = #p2p;
+
+          fn from_parts(
+              database: #db,
+              http: #http,
+              chain_interactor: #chain_interactor,
+              event_sink: #event_sink,
+              p2p: #p2p,
+          ) -> Self {
+              Self { #(#field_inits),* }
+          }
+
+          #(#accessors)*
         }
     );
 
     adapter_trait_impl.into()
 }
+
+/// Exports a function as an entrypoint or callback of a WASM binary. Every
+/// parameter is read from the result of the promise at the same index of the
+/// queue that scheduled the call, see `FromPromiseStatus`. A returned `Result`
+/// becomes the execution result, see `CallbackResult`.
+#[proc_macro_attribute]
+pub fn callback(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(proc_macro2::Span::call_site(), "A callback doesn't take arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let function = parse_macro_input!(item as ItemFn);
+    match callback::expand(function) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
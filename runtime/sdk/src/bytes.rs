@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::Result;
 
@@ -88,6 +88,26 @@ impl FromBytes for String {
     }
 }
 
+/// Converts any serde type to and from bytes, encoded as JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> ToBytes for Json<T> {
+    fn to_bytes(self) -> Bytes {
+        Bytes(serde_json::to_vec(&self.0).expect("Serializing to JSON failed"))
+    }
+}
+
+impl<T: DeserializeOwned> FromBytes for Json<T> {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(Json(serde_json::from_slice(bytes)?))
+    }
+
+    fn from_bytes_vec(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(bytes.as_slice())
+    }
+}
+
 macro_rules! bytes_impls_le_bytes {
     ($type_:ty, $num_bytes:expr) => {
         impl ToBytes for $type_ {
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Bytes, FromBytes, Result, ToBytes};
+
+/// A key with its value, the result of a fulfilled `db_scan` promise is a list
+/// of these ordered by key.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatabaseEntry {
+    pub key:   String,
+    pub value: Vec<u8>,
+}
+
+impl ToBytes for Vec<DatabaseEntry> {
+    fn to_bytes(self) -> Bytes {
+        serde_json::to_vec(&self)
+            .expect("Serializing database entries can't fail")
+            .to_bytes()
+    }
+}
+
+impl FromBytes for Vec<DatabaseEntry> {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    fn from_bytes_vec(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(bytes.as_slice())
+    }
+}
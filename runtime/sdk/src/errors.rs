@@ -10,6 +10,12 @@ pub enum SDKError {
 
     #[error(transparent)]
     NumBytesConversion(#[from] std::array::TryFromSliceError),
+
+    #[error(transparent)]
+    JsonConversion(#[from] serde_json::Error),
+
+    #[error("Promise is not fulfilled: {0}")]
+    PromiseNotFulfilled(String),
 }
 
 pub type Result<T, E = SDKError> = core::result::Result<T, E>;
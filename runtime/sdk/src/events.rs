@@ -10,10 +10,24 @@ pub enum EventData {
     ChainTick,
     P2PMessage(P2PMessage),
     CliCall(Vec<String>),
+    /// Runs an untrusted data request binary, always in limited mode
+    DataRequest(DataRequestEvent),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DataRequestEvent {
+    /// The name of the binary in the node config
+    pub program: String,
+    pub args:    Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Event {
-    pub id:   EventId,
-    pub data: EventData,
+    pub id:        EventId,
+    pub data:      EventData,
+    /// The consensus time of the event in ms since the unix epoch, e.g. the
+    /// start of the mainchain slot it was read from or the time of the event
+    /// that broadcast a P2P message. Never the clock of the node, so every
+    /// node that runs it sees the same time.
+    pub timestamp: u64,
 }
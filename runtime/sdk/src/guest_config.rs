@@ -0,0 +1,48 @@
+use clap::ValueEnum;
+use seda_config::{ChainConfigsInner, NodeConfigInner};
+use serde::{Deserialize, Serialize};
+
+use crate::Chain;
+
+/// The part of the node config a WASM binary gets to see, the secret keys of
+/// the node are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuestConfig {
+    pub contract_account_id: String,
+    pub public_key:          String,
+    pub signer_account_id:   String,
+    /// The chains the node has an RPC URL configured for
+    pub chains:              Vec<Chain>,
+}
+
+impl GuestConfig {
+    pub fn new(node_config: &NodeConfigInner, chain_configs: &ChainConfigsInner) -> Self {
+        let chains = Chain::value_variants()
+            .iter()
+            .copied()
+            .filter(|chain| {
+                let chain_rpc_url = match chain {
+                    Chain::Another => &chain_configs.another.chain_rpc_url,
+                    Chain::Near => &chain_configs.near.chain_rpc_url,
+                };
+
+                !chain_rpc_url.is_empty()
+            })
+            .collect();
+
+        Self {
+            contract_account_id: node_config.contract_account_id.clone(),
+            public_key: node_config.public_key.clone(),
+            signer_account_id: node_config.signer_account_id.clone(),
+            chains,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = ""]
+mod test {
+    use super::*;
+
+    mod guest_config_test;
+}
@@ -0,0 +1,18 @@
+use seda_config::{AnotherConfig, ChainConfigsInner, NodeConfigInner};
+
+use super::*;
+
+#[test]
+fn chains_are_the_configured_chains() {
+    let chain_configs = ChainConfigsInner {
+        another: AnotherConfig {
+            chain_rpc_url: String::new(),
+        },
+        ..(*ChainConfigsInner::test_config()).clone()
+    };
+
+    let guest_config = GuestConfig::new(&NodeConfigInner::test_config(), &chain_configs);
+
+    assert_eq!(guest_config.chains.len(), 1);
+    assert!(matches!(guest_config.chains[0], Chain::Near));
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Bytes, FromBytes, Result, ToBytes};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+    Options,
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpMethod::Get => write!(f, "GET"),
+            HttpMethod::Post => write!(f, "POST"),
+            HttpMethod::Put => write!(f, "PUT"),
+            HttpMethod::Patch => write!(f, "PATCH"),
+            HttpMethod::Delete => write!(f, "DELETE"),
+            HttpMethod::Head => write!(f, "HEAD"),
+            HttpMethod::Options => write!(f, "OPTIONS"),
+        }
+    }
+}
+
+/// The result of a fulfilled HTTP promise. Any response the server sends is
+/// considered fulfilled, including non 2xx status codes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub status:  u16,
+    /// The response headers, multiple values for the same header are joined
+    /// with a `,`
+    pub headers: BTreeMap<String, String>,
+    pub body:    Vec<u8>,
+}
+
+impl ToBytes for HttpResponse {
+    fn to_bytes(self) -> Bytes {
+        serde_json::to_vec(&self)
+            .expect("Serializing an HttpResponse can't fail")
+            .to_bytes()
+    }
+}
+
+impl FromBytes for HttpResponse {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    fn from_bytes_vec(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(bytes.as_slice())
+    }
+}
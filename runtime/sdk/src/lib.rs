@@ -1,7 +1,13 @@
 mod chain;
 pub use chain::Chain;
+mod database;
+pub use database::*;
 mod errors;
 pub use errors::*;
+mod guest_config;
+pub use guest_config::GuestConfig;
+mod http;
+pub use http::*;
 mod level;
 pub use level::Level;
 mod bytes;
@@ -18,12 +24,15 @@ pub use promises::{
     CallSelfAction,
     ChainCallAction,
     ChainViewAction,
+    DatabaseDeleteAction,
     DatabaseGetAction,
+    DatabaseScanAction,
     DatabaseSetAction,
     HttpAction,
     P2PBroadcastAction,
     Promise,
     PromiseAction,
+    PromiseCondition,
     PromiseStatus,
     TriggerEventAction,
 };
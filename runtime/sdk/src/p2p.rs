@@ -6,6 +6,15 @@ pub struct P2PMessage {
     pub data:   Vec<u8>,
 }
 
+/// What a node gossips for a broadcast: the data along with the consensus
+/// time of the event that broadcast it, which every receiving node runs the
+/// message at.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct P2PEnvelope {
+    pub timestamp: u64,
+    pub data:      Vec<u8>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct UnicastCommand {
     pub peer_id: String,
@@ -1,8 +1,11 @@
 use core::fmt;
+use std::collections::BTreeMap;
 
+#[cfg(not(target_family = "wasm"))]
+use seda_config::Capabilities;
 use serde::{Deserialize, Serialize};
 
-use crate::{events::Event, Chain};
+use crate::{events::Event, Chain, HttpMethod};
 
 // TODO: all action types with Vec<u8> can just be the Bytes type.
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -10,6 +13,8 @@ pub enum PromiseAction {
     CallSelf(CallSelfAction),
     DatabaseSet(DatabaseSetAction),
     DatabaseGet(DatabaseGetAction),
+    DatabaseDelete(DatabaseDeleteAction),
+    DatabaseScan(DatabaseScanAction),
     Http(HttpAction),
     ChainView(ChainViewAction),
     ChainCall(ChainCallAction),
@@ -18,9 +23,46 @@ pub enum PromiseAction {
 }
 
 impl PromiseAction {
+    /// The capability a binary in limited mode lacks to run the action, `None`
+    /// when the capabilities allow it. The binary may always use its own
+    /// database namespace, named after the program.
     #[cfg(not(target_family = "wasm"))]
-    pub fn is_limited_action(&self) -> bool {
-        !matches!(self, Self::CallSelf(_) | Self::Http(_))
+    pub fn missing_capability(&self, capabilities: &Capabilities, program_name: &str) -> Option<String> {
+        let action = self.to_string();
+        if !capabilities.allows_action(&action) {
+            return Some(format!("the `{action}` action"));
+        }
+
+        match self {
+            Self::Http(http_action) => match http_action.domain() {
+                Some(domain) => {
+                    (!capabilities.allows_http_domain(&domain)).then(|| format!("the `{domain}` HTTP domain"))
+                }
+                None => Some(format!("the HTTP URL `{}`, which has no valid host", http_action.url)),
+            },
+            Self::ChainView(ChainViewAction { chain, contract_id, .. })
+            | Self::ChainCall(ChainCallAction { chain, contract_id, .. }) => {
+                let chain = chain.to_string();
+                (!capabilities.allows_contract(&chain, contract_id))
+                    .then(|| format!("the `{contract_id}` contract on `{chain}`"))
+            }
+            Self::DatabaseSet(DatabaseSetAction { namespace, .. })
+            | Self::DatabaseGet(DatabaseGetAction { namespace, .. })
+            | Self::DatabaseDelete(DatabaseDeleteAction { namespace, .. })
+            | Self::DatabaseScan(DatabaseScanAction { namespace, .. }) => namespace
+                .as_ref()
+                .filter(|namespace| namespace.as_str() != program_name && !capabilities.allows_db_namespace(namespace))
+                .map(|namespace| format!("the `{namespace}` database namespace")),
+            Self::CallSelf(_) | Self::TriggerEvent(_) | Self::P2PBroadcast(_) => None,
+        }
+    }
+
+    /// Actions that don't depend on the promises queued before them, so they
+    /// can run at the same time. Database actions, chain calls and events keep
+    /// their order.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn is_independent_action(&self) -> bool {
+        matches!(self, Self::Http(_) | Self::ChainView(_))
     }
 }
 
@@ -30,6 +72,8 @@ impl fmt::Display for PromiseAction {
             Self::CallSelf(_) => write!(f, "call_self"),
             Self::DatabaseSet(_) => write!(f, "db_set"),
             Self::DatabaseGet(_) => write!(f, "db_get"),
+            Self::DatabaseDelete(_) => write!(f, "db_delete"),
+            Self::DatabaseScan(_) => write!(f, "db_scan"),
             Self::Http(_) => write!(f, "http"),
             Self::ChainView(_) => write!(f, "chain_view"),
             Self::ChainCall(_) => write!(f, "chain_call"),
@@ -43,23 +87,76 @@ impl fmt::Display for PromiseAction {
 pub struct CallSelfAction {
     pub function_name: String,
     pub args:          Vec<String>,
+    /// The typed input of the call, see `call_self_with` and `call_input`
+    #[serde(default)]
+    pub input:         Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DatabaseSetAction {
-    pub key:   String,
-    pub value: Vec<u8>,
+    pub key:       String,
+    pub value:     Vec<u8>,
+    /// Uses a namespace the node shares between binaries instead of the
+    /// namespace of the binary itself
+    pub namespace: Option<String>,
+    /// Writes to the database right away instead of when the execution
+    /// succeeds, the write is kept even when the execution fails later
+    #[serde(default)]
+    pub immediate: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DatabaseGetAction {
-    pub key: String,
+    pub key:       String,
+    /// Uses a namespace the node shares between binaries instead of the
+    /// namespace of the binary itself
+    pub namespace: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DatabaseDeleteAction {
+    pub key:       String,
+    /// Uses a namespace the node shares between binaries instead of the
+    /// namespace of the binary itself
+    pub namespace: Option<String>,
+    /// Writes to the database right away instead of when the execution
+    /// succeeds, the write is kept even when the execution fails later
+    #[serde(default)]
+    pub immediate: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DatabaseScanAction {
+    /// Every key that starts with the prefix is returned, an empty prefix
+    /// returns all the keys
+    pub prefix:    String,
+    /// Uses a namespace the node shares between binaries instead of the
+    /// namespace of the binary itself
+    pub namespace: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct HttpAction {
-    pub url: String,
-    // TODO: add headers, method, etc :)
+    pub url:        String,
+    pub method:     HttpMethod,
+    pub headers:    BTreeMap<String, String>,
+    pub body:       Option<Vec<u8>>,
+    /// Overrides the timeout of the request, the promise timeout of the node
+    /// still applies
+    pub timeout_ms: Option<u64>,
+}
+
+impl HttpAction {
+    /// The lowercased host of the URL, parsed by the same rules as the HTTP
+    /// client so it's the host that is actually requested. `None` when the
+    /// URL doesn't parse or has no host.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn domain(&self) -> Option<String> {
+        url::Url::parse(&self.url)
+            .ok()?
+            .host_str()
+            .map(|host| host.to_ascii_lowercase())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,90 @@
+use seda_config::Capabilities;
+
+use super::*;
+
+fn http(url: &str) -> PromiseAction {
+    PromiseAction::Http(HttpAction {
+        url: url.to_string(),
+        ..Default::default()
+    })
+}
+
+fn http_capabilities(domain: &str) -> Capabilities {
+    Capabilities {
+        actions: vec!["http".to_string()],
+        http_domains: vec![domain.to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn http_domain_is_the_requested_host() {
+    let domain = |url: &str| {
+        HttpAction {
+            url: url.to_string(),
+            ..Default::default()
+        }
+        .domain()
+    };
+
+    assert_eq!(
+        domain("https://user@Example.COM:8080/path"),
+        Some("example.com".to_string())
+    );
+    assert_eq!(domain("http://evil.com\\@good.com/"), Some("evil.com".to_string()));
+    assert_eq!(domain("not a url"), None);
+}
+
+#[test]
+fn http_allows_subdomains() {
+    let capabilities = http_capabilities("example.com");
+
+    assert_eq!(
+        http("https://api.example.com/").missing_capability(&capabilities, "program"),
+        None
+    );
+    assert_eq!(
+        http("https://notexample.com/").missing_capability(&capabilities, "program"),
+        Some("the `notexample.com` HTTP domain".to_string())
+    );
+}
+
+#[test]
+fn http_backslash_does_not_bypass_domain() {
+    let capabilities = http_capabilities("good.com");
+
+    assert_eq!(
+        http("http://evil.com\\@good.com/").missing_capability(&capabilities, "program"),
+        Some("the `evil.com` HTTP domain".to_string())
+    );
+}
+
+#[test]
+fn http_rejects_unparsable_url() {
+    let capabilities = http_capabilities(Capabilities::ANY);
+
+    assert_eq!(
+        http("//good.com/").missing_capability(&capabilities, "program"),
+        Some("the HTTP URL `//good.com/`, which has no valid host".to_string())
+    );
+}
+
+#[test]
+fn db_allows_own_namespace() {
+    let capabilities = Capabilities {
+        actions: vec!["db_get".to_string()],
+        ..Default::default()
+    };
+    let db_get = |namespace: &str| {
+        PromiseAction::DatabaseGet(DatabaseGetAction {
+            key:       "key".to_string(),
+            namespace: Some(namespace.to_string()),
+        })
+    };
+
+    assert_eq!(db_get("program").missing_capability(&capabilities, "program"), None);
+    assert_eq!(
+        db_get("other").missing_capability(&capabilities, "program"),
+        Some("the `other` database namespace".to_string())
+    );
+}
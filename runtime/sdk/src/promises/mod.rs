@@ -2,4 +2,12 @@ mod actions;
 mod promise;
 
 pub use actions::*;
-pub use promise::{Promise, PromiseStatus};
+pub use promise::{Promise, PromiseCondition, PromiseStatus};
+
+#[cfg(test)]
+#[path = ""]
+mod test {
+    use super::*;
+
+    mod actions_test;
+}
@@ -1,10 +1,12 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::PromiseAction;
 use crate::ToBytes;
 
 // TODO: Fulfilled and Rejected could now just be our Bytes type.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// WASM binaries read the status borsh encoded since version 1 of the ABI.
+#[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Clone, Debug)]
 pub enum PromiseStatus {
     /// Initial state
     Unfulfilled,
@@ -40,6 +42,31 @@ impl<T: crate::ToBytes, E: std::error::Error> From<Result<Option<T>, E>> for Pro
     }
 }
 
+/// When a chained promise runs, depending on the status of the promise queued
+/// before it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PromiseCondition {
+    /// Runs whatever the status of the previous promise
+    #[default]
+    Always,
+
+    /// Only runs when the previous promise was fulfilled
+    OnFulfilled,
+
+    /// Only runs when the previous promise was rejected
+    OnRejected,
+}
+
+impl PromiseCondition {
+    pub fn is_met(&self, previous_status: &PromiseStatus) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnFulfilled => matches!(previous_status, PromiseStatus::Fulfilled(_)),
+            Self::OnRejected => matches!(previous_status, PromiseStatus::Rejected(_)),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Promise {
     /// The name of the action we should execute
@@ -47,4 +74,10 @@ pub struct Promise {
 
     /// The status of the promise, will include the result if it's fulfilled
     pub status: PromiseStatus,
+
+    /// A promise whose condition isn't met is skipped and takes over the
+    /// status of the previous promise, so a rejection reaches the next
+    /// `OnRejected` promise of the queue
+    #[serde(default)]
+    pub condition: PromiseCondition,
 }
@@ -49,3 +49,100 @@ pub fn bn254_sign(message: &[u8], private_key: &Bn254PrivateKey) -> Bn254Signatu
 
     Bn254Signature::from_compressed(result_data_ptr).expect("Signature should be valid")
 }
+
+/// Signs with the secret key of the node, which the binary never sees.
+pub fn node_sign(message: &[u8]) -> Bn254Signature {
+    // Compressed Signatures in G1 have a length of 33 bytes
+    let value_len = 33;
+    let mut result_data_ptr = vec![0; value_len as usize];
+
+    unsafe {
+        raw::node_sign(
+            message.as_ptr(),
+            message.len() as i64,
+            result_data_ptr.as_mut_ptr(),
+            value_len,
+        )
+    };
+
+    Bn254Signature::from_compressed(result_data_ptr).expect("Signature should be valid")
+}
+
+fn concat_compressed<T>(values: &[T], to_compressed: impl Fn(&T) -> Vec<u8>) -> Vec<u8> {
+    values.iter().flat_map(to_compressed).collect()
+}
+
+/// Aggregates signatures of the same message, e.g. the signatures of a
+/// committee, into one signature.
+pub fn bn254_aggregate_signatures(signatures: &[Bn254Signature]) -> Bn254Signature {
+    let signatures_bytes = concat_compressed(signatures, |signature| {
+        signature.to_compressed().expect("Signature should be valid")
+    });
+
+    // Compressed Signatures in G1 have a length of 33 bytes
+    let value_len = 33;
+    let mut result_data_ptr = vec![0; value_len as usize];
+
+    unsafe {
+        raw::bn254_aggregate_signatures(
+            signatures_bytes.as_ptr(),
+            signatures_bytes.len() as i64,
+            result_data_ptr.as_mut_ptr(),
+            value_len,
+        )
+    };
+
+    Bn254Signature::from_compressed(result_data_ptr).expect("Signature should be valid")
+}
+
+/// Aggregates the public keys of the signers of an aggregate signature.
+pub fn bn254_aggregate_public_keys(public_keys: &[Bn254PublicKey]) -> Bn254PublicKey {
+    let public_keys_bytes = concat_compressed(public_keys, |public_key| {
+        public_key.to_compressed().expect("Public Key should be valid")
+    });
+
+    // Compressed Public Keys in G2 have a length of 65 bytes
+    let value_len = 65;
+    let mut result_data_ptr = vec![0; value_len as usize];
+
+    unsafe {
+        raw::bn254_aggregate_public_keys(
+            public_keys_bytes.as_ptr(),
+            public_keys_bytes.len() as i64,
+            result_data_ptr.as_mut_ptr(),
+            value_len,
+        )
+    };
+
+    Bn254PublicKey::from_compressed(result_data_ptr).expect("Public Key should be valid")
+}
+
+/// Verifies an aggregate signature against the public keys of all its
+/// signers.
+///
+/// The caller must make sure every public key comes with a proof of
+/// possession, e.g. by only passing registered committee keys, since a rogue
+/// key can otherwise forge the aggregate signature.
+pub fn bn254_verify_aggregate(message: &[u8], signature: &Bn254Signature, public_keys: &[Bn254PublicKey]) -> bool {
+    let signature_bytes = signature.to_compressed().expect("Signature should be valid");
+    let public_keys_bytes = concat_compressed(public_keys, |public_key| {
+        public_key.to_compressed().expect("Public Key should be valid")
+    });
+
+    let result = unsafe {
+        raw::bn254_verify_aggregate(
+            message.as_ptr(),
+            message.len() as i64,
+            signature_bytes.as_ptr(),
+            signature_bytes.len() as i64,
+            public_keys_bytes.as_ptr(),
+            public_keys_bytes.len() as i64,
+        )
+    };
+
+    match result {
+        0 => false,
+        1 => true,
+        _ => panic!("Bn254 verify aggregate returned invalid bool in u8: {}", result),
+    }
+}
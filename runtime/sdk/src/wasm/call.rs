@@ -1,9 +1,32 @@
-use super::Promise;
-use crate::{CallSelfAction, PromiseAction};
+use super::{raw, Promise};
+use crate::{CallSelfAction, FromBytes, PromiseAction, Result, ToBytes};
 
 pub fn call_self(function_name: &str, args: Vec<String>) -> Promise {
     Promise::new(PromiseAction::CallSelf(CallSelfAction {
         function_name: function_name.to_string(),
         args,
+        input: Vec::new(),
     }))
 }
+
+/// Calls a function of this binary with a typed input, the function reads it
+/// back with [call_input]. Use [Json](crate::Json) for serde types.
+pub fn call_self_with<T: ToBytes>(function_name: &str, input: T) -> Promise {
+    Promise::new(PromiseAction::CallSelf(CallSelfAction {
+        function_name: function_name.to_string(),
+        args:          Vec::new(),
+        input:         input.to_bytes().eject(),
+    }))
+}
+
+/// Reads the input the current function was called with, see
+/// [call_self_with].
+pub fn call_input<T: FromBytes>() -> Result<T> {
+    let input_length = unsafe { raw::call_input_length() };
+    let mut input = vec![0; input_length as usize];
+    unsafe {
+        raw::call_input_write(input.as_mut_ptr(), input_length);
+    }
+
+    T::from_bytes_vec(input)
+}
@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+pub use seda_runtime_macros::callback;
+
+use super::execution_result;
+use crate::{FromBytes, PromiseStatus, Result, SDKError, ToBytes};
+
+/// Converts the result of a promise into a parameter of a [callback].
+pub trait FromPromiseStatus: Sized {
+    fn from_promise_status(status: PromiseStatus) -> Result<Self>;
+}
+
+impl FromPromiseStatus for PromiseStatus {
+    fn from_promise_status(status: PromiseStatus) -> Result<Self> {
+        Ok(status)
+    }
+}
+
+/// The value of a fulfilled promise.
+impl<T: FromBytes> FromPromiseStatus for T {
+    fn from_promise_status(status: PromiseStatus) -> Result<Self> {
+        match status {
+            PromiseStatus::Fulfilled(bytes) => T::from_bytes_vec(bytes.unwrap_or_default()),
+            status => Err(SDKError::PromiseNotFulfilled(format!("{status:?}"))),
+        }
+    }
+}
+
+/// The value of a fulfilled promise or the error of a rejected one.
+impl<T: FromBytes> FromPromiseStatus for std::result::Result<T, String> {
+    fn from_promise_status(status: PromiseStatus) -> Result<Self> {
+        match status {
+            PromiseStatus::Fulfilled(bytes) => Ok(Ok(T::from_bytes_vec(bytes.unwrap_or_default())?)),
+            PromiseStatus::Rejected(bytes) => Ok(Err(String::from_utf8_lossy(&bytes).into_owned())),
+            status => Err(SDKError::PromiseNotFulfilled(format!("{status:?}"))),
+        }
+    }
+}
+
+/// What a [callback] does with the value it returns.
+pub trait CallbackResult {
+    fn into_execution_result(self);
+}
+
+impl CallbackResult for () {
+    fn into_execution_result(self) {}
+}
+
+/// `Ok` becomes the execution result, `Err` fails the execution.
+impl<T: ToBytes, E: Display> CallbackResult for std::result::Result<T, E> {
+    fn into_execution_result(self) {
+        match self {
+            Ok(value) => execution_result(value.to_bytes().eject()),
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
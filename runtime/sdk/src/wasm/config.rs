@@ -1,12 +1,14 @@
 use lazy_static::lazy_static;
-use seda_config::{NodeConfig, NodeConfigInner};
 
-fn config() -> NodeConfig {
-    let config_str = std::env::var("WASM_NODE_CONFIG").expect("ENV DNE");
-    NodeConfigInner::from_json_str(&config_str)
+use super::{host::host_result, raw};
+use crate::GuestConfig;
+
+fn config() -> GuestConfig {
+    let config_length = unsafe { raw::config_length() };
+    serde_json::from_slice(&host_result(config_length)).expect("The guest config is always valid JSON")
 }
 
 // Lazy static so its only converting from bytes once per wasm bin
 lazy_static! {
-    pub static ref CONFIG: NodeConfig = config();
+    pub static ref CONFIG: GuestConfig = config();
 }
@@ -0,0 +1,97 @@
+//! Hashing and signature verification run by the host, so binaries don't have
+//! to compile the crypto libraries to WASM.
+use super::raw;
+
+pub fn sha256(message: &[u8]) -> [u8; 32] {
+    let mut result = [0; 32];
+    unsafe {
+        raw::sha256(
+            message.as_ptr(),
+            message.len() as i64,
+            result.as_mut_ptr(),
+            result.len() as i64,
+        );
+    }
+
+    result
+}
+
+/// Keccak-256 as used by Ethereum, not the standardized SHA3-256.
+pub fn keccak256(message: &[u8]) -> [u8; 32] {
+    let mut result = [0; 32];
+    unsafe {
+        raw::keccak256(
+            message.as_ptr(),
+            message.len() as i64,
+            result.as_mut_ptr(),
+            result.len() as i64,
+        );
+    }
+
+    result
+}
+
+fn to_bool(result: u8, import: &str) -> bool {
+    match result {
+        0 => false,
+        1 => true,
+        _ => panic!("{import} returned invalid bool in u8: {result}"),
+    }
+}
+
+/// Verifies a compact `secp256k1` ECDSA signature of the message hash, the
+/// public key is compressed (33 bytes) or uncompressed (65 bytes).
+pub fn secp256k1_verify(message_hash: &[u8; 32], signature: &[u8; 64], public_key: &[u8]) -> bool {
+    let result = unsafe {
+        raw::secp256k1_verify(
+            message_hash.as_ptr(),
+            message_hash.len() as i64,
+            signature.as_ptr(),
+            signature.len() as i64,
+            public_key.as_ptr(),
+            public_key.len() as i64,
+        )
+    };
+
+    to_bool(result, "Secp256k1 verify")
+}
+
+/// Recovers the public key that signed the message hash, `None` when the
+/// signature is invalid. Returns the compressed key (33 bytes) or the
+/// uncompressed key (65 bytes) that Ethereum addresses are derived from.
+pub fn secp256k1_recover(
+    message_hash: &[u8; 32],
+    signature: &[u8; 64],
+    recovery_id: u8,
+    compressed: bool,
+) -> Option<Vec<u8>> {
+    let mut result = if compressed { vec![0; 33] } else { vec![0; 65] };
+    let recovered = unsafe {
+        raw::secp256k1_recover(
+            message_hash.as_ptr(),
+            message_hash.len() as i64,
+            signature.as_ptr(),
+            signature.len() as i64,
+            recovery_id.into(),
+            result.as_mut_ptr(),
+            result.len() as i64,
+        )
+    };
+
+    to_bool(recovered, "Secp256k1 recover").then_some(result)
+}
+
+pub fn ed25519_verify(message: &[u8], signature: &[u8; 64], public_key: &[u8; 32]) -> bool {
+    let result = unsafe {
+        raw::ed25519_verify(
+            message.as_ptr(),
+            message.len() as i64,
+            signature.as_ptr(),
+            signature.len() as i64,
+            public_key.as_ptr(),
+            public_key.len() as i64,
+        )
+    };
+
+    to_bool(result, "Ed25519 verify")
+}
@@ -1,13 +1,138 @@
-use super::Promise;
-use crate::{DatabaseGetAction, DatabaseSetAction, PromiseAction};
+use super::{host::host_result, raw, Promise};
+use crate::{DatabaseDeleteAction, DatabaseGetAction, DatabaseScanAction, DatabaseSetAction, PromiseAction};
 
-pub fn db_set(key: &str, value: &str) -> Promise {
+/// Stores the value under the key, replacing the previous value.
+pub fn db_set<V: AsRef<[u8]>>(key: &str, value: V) -> Promise {
+    set(None, key, value)
+}
+
+/// Fulfills with the value of the key, or with nothing when it isn't set.
+pub fn db_get(key: &str) -> Promise {
+    get(None, key)
+}
+
+pub fn db_delete(key: &str) -> Promise {
+    delete(None, key)
+}
+
+/// Fulfills with the [crate::DatabaseEntry]s of every key that starts with the
+/// prefix.
+pub fn db_scan(prefix: &str) -> Promise {
+    scan(None, prefix)
+}
+
+/// Like [db_set], but in a namespace the node shares between binaries.
+pub fn shared_db_set<V: AsRef<[u8]>>(namespace: &str, key: &str, value: V) -> Promise {
+    set(Some(namespace), key, value)
+}
+
+/// Like [db_get], but in a namespace the node shares between binaries.
+pub fn shared_db_get(namespace: &str, key: &str) -> Promise {
+    get(Some(namespace), key)
+}
+
+/// Like [db_delete], but in a namespace the node shares between binaries.
+pub fn shared_db_delete(namespace: &str, key: &str) -> Promise {
+    delete(Some(namespace), key)
+}
+
+/// Like [db_scan], but in a namespace the node shares between binaries.
+pub fn shared_db_scan(namespace: &str, prefix: &str) -> Promise {
+    scan(Some(namespace), prefix)
+}
+
+/// Reads the value of the key right away, see [db_get] for the promise
+/// version. Sees the writes of the execution that aren't committed yet.
+pub fn db_get_sync(key: &str) -> Option<Vec<u8>> {
+    get_sync("", key)
+}
+
+/// Stores the value under the key without a promise round trip, it is
+/// committed with the other writes of the execution.
+pub fn db_set_sync<V: AsRef<[u8]>>(key: &str, value: V) {
+    set_sync("", key, value)
+}
+
+/// Like [db_get_sync], but in a namespace the node shares between binaries.
+pub fn shared_db_get_sync(namespace: &str, key: &str) -> Option<Vec<u8>> {
+    get_sync(namespace, key)
+}
+
+/// Like [db_set_sync], but in a namespace the node shares between binaries.
+pub fn shared_db_set_sync<V: AsRef<[u8]>>(namespace: &str, key: &str, value: V) {
+    set_sync(namespace, key, value)
+}
+
+impl Promise {
+    /// Makes a `db_set` or `db_delete` promise write to the database right
+    /// away. Other writes are only committed once the whole execution
+    /// succeeds.
+    pub fn immediate(mut self) -> Self {
+        match &mut self.action {
+            PromiseAction::DatabaseSet(action) => action.immediate = true,
+            PromiseAction::DatabaseDelete(action) => action.immediate = true,
+            action => panic!("Only database writes can be immediate, not `{action}`"),
+        }
+
+        self
+    }
+}
+
+fn set<V: AsRef<[u8]>>(namespace: Option<&str>, key: &str, value: V) -> Promise {
     Promise::new(PromiseAction::DatabaseSet(DatabaseSetAction {
-        key:   key.to_string(),
-        value: value.to_string().into_bytes(),
+        key:       key.to_string(),
+        value:     value.as_ref().to_vec(),
+        namespace: namespace.map(str::to_string),
+        immediate: false,
     }))
 }
 
-pub fn db_get(key: &str) -> Promise {
-    Promise::new(PromiseAction::DatabaseGet(DatabaseGetAction { key: key.to_string() }))
+fn get(namespace: Option<&str>, key: &str) -> Promise {
+    Promise::new(PromiseAction::DatabaseGet(DatabaseGetAction {
+        key:       key.to_string(),
+        namespace: namespace.map(str::to_string),
+    }))
+}
+
+fn delete(namespace: Option<&str>, key: &str) -> Promise {
+    Promise::new(PromiseAction::DatabaseDelete(DatabaseDeleteAction {
+        key:       key.to_string(),
+        namespace: namespace.map(str::to_string),
+        immediate: false,
+    }))
+}
+
+// An empty namespace is the namespace of the binary itself
+fn get_sync(namespace: &str, key: &str) -> Option<Vec<u8>> {
+    let value_length = unsafe {
+        raw::db_get_sync_length(
+            namespace.as_ptr(),
+            namespace.len() as i64,
+            key.as_ptr(),
+            key.len() as i64,
+        )
+    };
+
+    (value_length >= 0).then(|| host_result(value_length))
+}
+
+fn set_sync<V: AsRef<[u8]>>(namespace: &str, key: &str, value: V) {
+    let value = value.as_ref();
+    unsafe {
+        raw::db_set_sync(
+            namespace.as_ptr(),
+            namespace.len() as i64,
+            key.as_ptr(),
+            key.len() as i64,
+            value.as_ptr(),
+            value.len() as i64,
+        );
+    }
+}
+
+fn scan(namespace: Option<&str>, prefix: &str) -> Promise {
+    Promise::new(PromiseAction::DatabaseScan(DatabaseScanAction {
+        prefix:    prefix.to_string(),
+        namespace: namespace.map(str::to_string),
+    }))
 }
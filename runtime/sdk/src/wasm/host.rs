@@ -0,0 +1,56 @@
+//! Host functions that return their value directly, they block the WASM
+//! instance until the host answered instead of queueing a promise.
+use super::raw;
+
+/// Reads the value of the last synchronous host function that returned data.
+pub(crate) fn host_result(length: i64) -> Vec<u8> {
+    let mut result = vec![0; length as usize];
+    unsafe {
+        raw::host_result_write(result.as_mut_ptr(), length);
+    }
+
+    result
+}
+
+/// The current time in ms since the unix epoch, differs per node so it isn't
+/// available in a limited runtime, see [consensus_time_ms].
+pub fn time_now_ms() -> u64 {
+    unsafe { raw::time_now() as u64 }
+}
+
+/// Fills the buffer with random bytes from the host, differs per node so it
+/// isn't available in a limited runtime, see [deterministic_random_fill].
+pub fn random_fill(buffer: &mut [u8]) {
+    unsafe {
+        raw::random_fill(buffer.as_mut_ptr(), buffer.len() as i64);
+    }
+}
+
+pub fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    random_fill(&mut bytes);
+
+    bytes
+}
+
+/// The consensus time of the execution in ms since the unix epoch, the
+/// timestamp of the event it runs. Every node that runs the event sees the
+/// same time.
+pub fn consensus_time_ms() -> u64 {
+    unsafe { raw::consensus_time() as u64 }
+}
+
+/// Fills the buffer from a RNG seeded by the event, every node that runs the
+/// event draws the same bytes.
+pub fn deterministic_random_fill(buffer: &mut [u8]) {
+    unsafe {
+        raw::deterministic_random_fill(buffer.as_mut_ptr(), buffer.len() as i64);
+    }
+}
+
+pub fn deterministic_random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    deterministic_random_fill(&mut bytes);
+
+    bytes
+}
@@ -1,6 +1,18 @@
 use super::Promise;
 use crate::{HttpAction, PromiseAction};
 
+/// Does a GET request to the given url, the result is an
+/// [HttpResponse](crate::HttpResponse). Redirects aren't followed, the
+/// redirect response is the result.
 pub fn http_fetch(url: &str) -> Promise {
-    Promise::new(PromiseAction::Http(HttpAction { url: url.into() }))
+    http_request(HttpAction {
+        url: url.into(),
+        ..Default::default()
+    })
+}
+
+/// Does an HTTP request with the method, headers and body of the action, the
+/// result is an [HttpResponse](crate::HttpResponse).
+pub fn http_request(action: HttpAction) -> Promise {
+    Promise::new(PromiseAction::Http(action))
 }
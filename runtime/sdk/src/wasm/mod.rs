@@ -1,11 +1,14 @@
 mod bn254;
 mod call;
+mod callback;
 #[cfg(feature = "full")]
 mod chain_interactor;
 mod config;
+mod crypto;
 #[cfg(feature = "full")]
 mod database;
 mod execution;
+mod host;
 mod http;
 mod log;
 mod memory;
@@ -14,12 +17,15 @@ mod promise;
 mod raw;
 
 pub use call::*;
+pub use callback::*;
 #[cfg(feature = "full")]
 pub use chain_interactor::*;
 pub use config::CONFIG;
+pub use crypto::*;
 #[cfg(feature = "full")]
 pub use database::*;
 pub use execution::*;
+pub use host::*;
 pub use http::*;
 pub use log::*;
 pub use memory::*;
@@ -1,9 +1,10 @@
 use std::str;
 
+use borsh::BorshDeserialize;
 use serde::{Deserialize, Serialize};
 
 use super::raw::promise_then;
-use crate::{wasm::raw, PromiseAction, PromiseStatus};
+use crate::{wasm::raw, PromiseAction, PromiseCondition, PromiseStatus};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Promise {
@@ -13,6 +14,10 @@ pub struct Promise {
     /// The status of the promise, will include the result if it's fulfilled
     pub status: PromiseStatus,
 
+    /// When the runtime runs the promise, see [Promise::and_then] and
+    /// [Promise::catch]
+    pub condition: PromiseCondition,
+
     #[serde(skip)]
     /// The promise we should execute after this one
     pub after: Option<Box<Self>>,
@@ -24,6 +29,7 @@ impl Promise {
             action,
             after: None,
             status: PromiseStatus::Unfulfilled,
+            condition: PromiseCondition::Always,
         }
     }
 
@@ -45,7 +51,33 @@ impl Promise {
     }
 
     /// Chains this promise after the previous promise
-    pub fn then(mut self, after: Self) -> Self {
+    pub fn then(self, after: Self) -> Self {
+        self.chain(after, PromiseCondition::Always)
+    }
+
+    /// Chains this promise after the previous promise, it only runs when the
+    /// previous promise was fulfilled. Otherwise it's skipped and takes over
+    /// the rejection of the previous promise.
+    pub fn and_then(self, after: Self) -> Self {
+        self.chain(after, PromiseCondition::OnFulfilled)
+    }
+
+    /// Chains this promise after the previous promise, it only runs when the
+    /// previous promise was rejected, or when a rejection was passed on by the
+    /// promises that were skipped before it.
+    pub fn catch(self, after: Self) -> Self {
+        self.chain(after, PromiseCondition::OnRejected)
+    }
+
+    /// Chains this promise after the previous promise, it always runs. Same as
+    /// [Promise::then], reads better at the end of a chain of
+    /// [Promise::and_then] and [Promise::catch].
+    pub fn finally(self, after: Self) -> Self {
+        self.chain(after, PromiseCondition::Always)
+    }
+
+    fn chain(mut self, mut after: Self, condition: PromiseCondition) -> Self {
+        after.condition = condition;
         Promise::add_to_queue(&after);
         self.after = Some(Box::new(after));
 
@@ -54,18 +86,15 @@ impl Promise {
 
     /// Returns the result of a promise action
     pub fn result(index: i32) -> PromiseStatus {
-        let promise_result_length = unsafe { raw::promise_status_length(index) };
+        let promise_result_length = unsafe { raw::promise_status_length_v1(index) };
 
         let mut result_data: Vec<u8> = Vec::new();
         result_data.resize(promise_result_length as usize, 0);
 
         unsafe {
-            raw::promise_status_write(index, result_data.as_mut_ptr(), promise_result_length);
+            raw::promise_status_write_v1(index, result_data.as_mut_ptr(), promise_result_length);
         }
 
-        let result_str = String::from_utf8(result_data).unwrap();
-        let promise_status: PromiseStatus = serde_json::from_str(&result_str).unwrap();
-
-        promise_status
+        PromiseStatus::try_from_slice(&result_data).unwrap()
     }
 }
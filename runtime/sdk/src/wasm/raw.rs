@@ -1,11 +1,28 @@
 extern "C" {
     pub fn promise_then(action_data_offset: *const u8, action_data_length: i32);
-    pub fn promise_status_length(promise_index: i32) -> i64;
-    pub fn promise_status_write(promise_index: i32, result_data_offset: *const u8, result_data_length: i64);
+    pub fn promise_status_length_v1(promise_index: i32) -> i64;
+    pub fn promise_status_write_v1(promise_index: i32, result_data_offset: *const u8, result_data_length: i64);
     pub fn memory_read(key: *const u8, key_length: i64, result_data_ptr: *const u8, result_data_length: i64);
     pub fn memory_read_length(key: *const u8, key_length: i64) -> i64;
     pub fn memory_write(key: *const u8, key_length: i64, value: *const u8, value_length: i64);
     pub fn execution_result(result: *const u8, result_length: i32);
+    pub fn call_input_length() -> i64;
+    pub fn call_input_write(result_data_ptr: *const u8, result_data_length: i64);
+    pub fn db_get_sync_length(namespace: *const u8, namespace_length: i64, key: *const u8, key_length: i64) -> i64;
+    pub fn db_set_sync(
+        namespace: *const u8,
+        namespace_length: i64,
+        key: *const u8,
+        key_length: i64,
+        value: *const u8,
+        value_length: i64,
+    );
+    pub fn config_length() -> i64;
+    pub fn host_result_write(result_data_ptr: *const u8, result_data_length: i64);
+    pub fn time_now() -> i64;
+    pub fn random_fill(result_data_ptr: *const u8, result_data_length: i64);
+    pub fn consensus_time() -> i64;
+    pub fn deterministic_random_fill(result_data_ptr: *const u8, result_data_length: i64);
     pub fn _log(
         level: *const u8,
         level_len: i32,
@@ -30,4 +47,52 @@ extern "C" {
         result_data_ptr: *const u8,
         result_data_length: i64,
     );
+    pub fn node_sign(message: *const u8, message_length: i64, result_data_ptr: *const u8, result_data_length: i64);
+    pub fn bn254_aggregate_signatures(
+        signatures: *const u8,
+        signatures_length: i64,
+        result_data_ptr: *const u8,
+        result_data_length: i64,
+    );
+    pub fn bn254_aggregate_public_keys(
+        public_keys: *const u8,
+        public_keys_length: i64,
+        result_data_ptr: *const u8,
+        result_data_length: i64,
+    );
+    pub fn bn254_verify_aggregate(
+        message: *const u8,
+        message_length: i64,
+        signature: *const u8,
+        signature_length: i64,
+        public_keys: *const u8,
+        public_keys_length: i64,
+    ) -> u8;
+    pub fn sha256(message: *const u8, message_length: i64, result_data_ptr: *const u8, result_data_length: i64);
+    pub fn keccak256(message: *const u8, message_length: i64, result_data_ptr: *const u8, result_data_length: i64);
+    pub fn secp256k1_verify(
+        message_hash: *const u8,
+        message_hash_length: i64,
+        signature: *const u8,
+        signature_length: i64,
+        public_key: *const u8,
+        public_key_length: i64,
+    ) -> u8;
+    pub fn secp256k1_recover(
+        message_hash: *const u8,
+        message_hash_length: i64,
+        signature: *const u8,
+        signature_length: i64,
+        recovery_id: i32,
+        result_data_ptr: *const u8,
+        result_data_length: i64,
+    ) -> u8;
+    pub fn ed25519_verify(
+        message: *const u8,
+        message_length: i64,
+        signature: *const u8,
+        signature_length: i64,
+        public_key: *const u8,
+        public_key_length: i64,
+    ) -> u8;
 }
@@ -1,9 +1,9 @@
 use clap::{Parser, Subcommand};
 use seda_runtime_sdk::{
-    wasm::{call_self, chain_call, chain_view, db_set, http_fetch, log, p2p_broadcast_message, Promise},
+    wasm::{call_self, callback, chain_call, chain_view, db_set, http_fetch, log, p2p_broadcast_message},
     Chain,
     FromBytes,
-    PromiseStatus,
+    HttpResponse,
 };
 
 #[derive(Debug, Parser)]
@@ -81,37 +81,27 @@ fn main() {
     }
 }
 
-#[no_mangle]
-fn http_fetch_result() {
-    let result = Promise::result(0);
-
+#[callback]
+fn http_fetch_result(result: Result<HttpResponse, String>) {
     let value_to_store: String = match result {
-        PromiseStatus::Fulfilled(Some(vec)) => String::from_bytes_vec(vec).unwrap(),
-        _ => "Promise failed..".to_string(),
+        Ok(response) => String::from_bytes_vec(response.body).unwrap(),
+        Err(_) => "Promise failed..".to_string(),
     };
 
     println!("Value: {value_to_store}");
 }
 
-#[no_mangle]
-fn chain_view_test_success() {
-    let result = Promise::result(0);
-    let value_to_store: String = match result {
-        PromiseStatus::Fulfilled(Some(vec)) => String::from_bytes_vec(vec).unwrap(),
-        _ => "Promise failed..".to_string(),
-    };
+#[callback]
+fn chain_view_test_success(result: Result<String, String>) {
+    let value_to_store = result.unwrap_or_else(|_| "Promise failed..".to_string());
     println!("Value: {value_to_store}");
 
     db_set("chain_view_result", &value_to_store).start();
 }
 
-#[no_mangle]
-fn chain_call_test_success() {
-    let result = Promise::result(0);
-    let value_to_store: String = match result {
-        PromiseStatus::Fulfilled(Some(vec)) => String::from_bytes_vec(vec).unwrap(),
-        _ => "Promise failed..".to_string(),
-    };
+#[callback]
+fn chain_call_test_success(result: Result<String, String>) {
+    let value_to_store = result.unwrap_or_else(|_| "Promise failed..".to_string());
     println!("Value: {value_to_store}");
     db_set("chain_call_result", &value_to_store).start();
 }
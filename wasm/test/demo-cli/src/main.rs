@@ -3,6 +3,7 @@ use seda_runtime_sdk::{
     wasm::{call_self, chain_call, chain_view, db_set, http_fetch, Promise},
     Chain,
     FromBytes,
+    HttpResponse,
     PromiseStatus,
 };
 
@@ -82,7 +83,10 @@ fn http_fetch_result() {
     let result = Promise::result(0);
 
     let value_to_store: String = match result {
-        PromiseStatus::Fulfilled(Some(vec)) => String::from_bytes_vec(vec).unwrap(),
+        PromiseStatus::Fulfilled(Some(vec)) => {
+            let response = HttpResponse::from_bytes_vec(vec).unwrap();
+            String::from_bytes_vec(response.body).unwrap()
+        }
         _ => "Promise failed..".to_string(),
     };
 
@@ -1,23 +1,55 @@
-use std::{env, fmt::Write, num::ParseIntError};
+use std::{
+    env,
+    fmt::Write,
+    num::ParseIntError,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use seda_runtime_sdk::{
     wasm::{
+        bn254_aggregate_public_keys,
+        bn254_aggregate_signatures,
         bn254_sign,
         bn254_verify,
+        bn254_verify_aggregate,
+        call_input,
         call_self,
+        call_self_with,
+        callback,
+        consensus_time_ms,
+        db_delete,
         db_get,
+        db_get_sync,
+        db_scan,
         db_set,
+        db_set_sync,
+        deterministic_random_bytes,
+        ed25519_verify,
         execution_result,
         http_fetch,
+        http_request,
+        keccak256,
         memory_read,
         memory_write,
+        node_sign,
+        random_bytes,
+        secp256k1_recover,
+        secp256k1_verify,
+        sha256,
+        shared_db_set,
+        time_now_ms,
         Bn254PrivateKey,
         Bn254PublicKey,
         Bn254Signature,
         Promise,
         CONFIG,
     },
+    DatabaseEntry,
     FromBytes,
+    HttpAction,
+    HttpMethod,
+    HttpResponse,
+    Json,
     PromiseStatus,
     ToBytes,
 };
@@ -54,6 +86,129 @@ fn completed_all() {
     Promise::result(2);
 }
 
+#[no_mangle]
+fn db_scan_test() {
+    db_set("scan_a", "1")
+        .start()
+        .then(db_set("scan_b", "2"))
+        .then(db_set("not_scanned", "3"))
+        .then(db_delete("scan_b"))
+        .then(db_scan("scan_"))
+        .then(call_self("db_scan_test_success", vec![]));
+}
+
+#[no_mangle]
+fn db_scan_test_success() {
+    let result = Promise::result(4);
+
+    if let PromiseStatus::Fulfilled(Some(bytes)) = result {
+        let entries = Vec::<DatabaseEntry>::from_bytes_vec(bytes).unwrap();
+        let keys: Vec<String> = entries.into_iter().map(|entry| entry.key).collect();
+
+        db_set("db_scan_result", keys.join(",")).start();
+    }
+}
+
+#[no_mangle]
+fn db_namespace_test() {
+    db_set("namespace_own", "own")
+        .start()
+        .then(shared_db_set("shared", "namespace_shared", "shared"))
+        .then(shared_db_set("not_shared", "namespace_not_shared", "not_shared"))
+        .then(call_self("db_namespace_test_success", vec![]));
+}
+
+#[no_mangle]
+fn db_namespace_test_success() {
+    if let PromiseStatus::Rejected(_) = Promise::result(2) {
+        db_set("namespace_rejected", "true").start();
+    }
+}
+
+#[no_mangle]
+fn promise_catch_test() {
+    // The namespace isn't shared so the first promise gets rejected
+    shared_db_set("not_shared", "catch_rejected", "rejected")
+        .start()
+        .and_then(db_set("catch_skipped", "skipped"))
+        .catch(db_set("catch_caught", "caught"))
+        .and_then(db_set("catch_recovered", "recovered"))
+        .finally(db_set("catch_finally", "finally"));
+}
+
+#[no_mangle]
+fn db_transaction_test() {
+    db_set("transaction_buffered", "buffered")
+        .start()
+        .then(db_set("transaction_immediate", "immediate").immediate())
+        .then(db_get("transaction_buffered"))
+        .then(call_self("db_transaction_test_fail", vec![]));
+}
+
+#[no_mangle]
+fn db_transaction_test_fail() {
+    // The execution sees its own writes before they are committed
+    if let PromiseStatus::Fulfilled(Some(value)) = Promise::result(2) {
+        println!("Read buffered value: {}", String::from_bytes_vec(value).unwrap());
+    }
+
+    panic!("failing after the database writes");
+}
+
+#[no_mangle]
+fn call_input_test() {
+    call_self_with("call_input_test_success", Json((42u128, "typed".to_string()))).start();
+}
+
+#[no_mangle]
+fn call_input_test_success() {
+    let Json((number, text)): Json<(u128, String)> = call_input().unwrap();
+
+    db_set("call_input_result", format!("{number} {text}")).start();
+}
+
+#[no_mangle]
+fn callback_test() {
+    db_set("callback_value", "42")
+        .start()
+        .then(db_get("callback_value"))
+        .then(call_self("callback_test_success", vec![]));
+}
+
+#[callback]
+fn callback_test_success(_set: PromiseStatus, value: String) -> Result<String, String> {
+    Ok(format!("Value: {value}"))
+}
+
+#[no_mangle]
+fn sync_host_test() {
+    // No promise round trips, every value is returned right away
+    db_set_sync("sync_value", "sync");
+    let value = db_get_sync("sync_value").unwrap();
+    assert!(db_get_sync("sync_missing").is_none());
+    assert!(time_now_ms() > 0);
+    assert_eq!(random_bytes(32).len(), 32);
+
+    execution_result(value);
+}
+
+#[no_mangle]
+fn deterministic_host_test() {
+    // The WASI clock of a limited runtime reads the consensus time as well
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+    assert_eq!(now_ms, consensus_time_ms());
+
+    let mut result = consensus_time_ms().to_le_bytes().to_vec();
+    result.extend(deterministic_random_bytes(32));
+    execution_result(result);
+}
+
+#[no_mangle]
+fn call_self_loop_test() {
+    // Schedules a new generation of promises forever
+    call_self("call_self_loop_test", vec![]).start();
+}
+
 #[no_mangle]
 fn http_fetch_test() {
     let args: Vec<String> = env::args().collect();
@@ -69,12 +224,60 @@ fn http_fetch_test_success() {
     let result = Promise::result(0);
 
     if let PromiseStatus::Fulfilled(Some(bytes)) = result {
-        let value_to_store = String::from_bytes_vec(bytes).unwrap();
+        let response = HttpResponse::from_bytes_vec(bytes).unwrap();
+        let value_to_store = String::from_bytes_vec(response.body).unwrap();
 
         db_set("http_fetch_result", &value_to_store).start();
     }
 }
 
+#[no_mangle]
+fn http_fetch_parallel_test() {
+    let args: Vec<String> = env::args().collect();
+
+    http_fetch(args.get(1).unwrap())
+        .start()
+        .then(http_fetch(args.get(2).unwrap()))
+        .then(call_self("http_fetch_parallel_test_success", vec![]));
+}
+
+#[no_mangle]
+fn http_fetch_parallel_test_success() {
+    for index in 0..2 {
+        if let PromiseStatus::Fulfilled(Some(bytes)) = Promise::result(index) {
+            let response = HttpResponse::from_bytes_vec(bytes).unwrap();
+            db_set(&format!("http_fetch_parallel_{index}"), response.body).start();
+        }
+    }
+}
+
+#[no_mangle]
+fn http_post_test() {
+    let args: Vec<String> = env::args().collect();
+
+    http_request(HttpAction {
+        url:        args.get(1).unwrap().clone(),
+        method:     HttpMethod::Post,
+        headers:    [("content-type".to_string(), "application/json".to_string())].into(),
+        body:       Some(args.get(2).unwrap().clone().into_bytes()),
+        timeout_ms: Some(5_000),
+    })
+    .start()
+    .then(call_self("http_post_test_success", vec![]));
+}
+
+#[no_mangle]
+fn http_post_test_success() {
+    let result = Promise::result(0);
+
+    if let PromiseStatus::Fulfilled(Some(bytes)) = result {
+        let response = HttpResponse::from_bytes_vec(bytes).unwrap();
+
+        db_set("http_post_status", &response.status.to_string()).start();
+        db_set("http_post_result", &String::from_bytes_vec(response.body).unwrap()).start();
+    }
+}
+
 #[no_mangle]
 fn memory_adapter_test_success() {
     let key = "u8";
@@ -117,6 +320,29 @@ fn test_limited_runtime() {
     db_set("foo", "bar").start().then(call_self("test_rejected", vec![]));
 }
 
+#[no_mangle]
+fn capabilities_test() {
+    // Queued together, the rejections say which capability is missing
+    db_set("capabilities", "allowed").start();
+    http_fetch("https://user@not-allowed.org:8080/path").start();
+    shared_db_set("other_program", "capabilities", "denied").start();
+    db_get("capabilities")
+        .start()
+        .then(call_self("capabilities_test_result", vec![]));
+}
+
+#[no_mangle]
+fn capabilities_test_result() {
+    let rejections: Vec<String> = (0..4)
+        .map(|index| match Promise::result(index) {
+            PromiseStatus::Rejected(rejected) => String::from_bytes(&rejected).unwrap(),
+            _ => String::new(),
+        })
+        .collect();
+
+    execution_result(rejections.join("\n").into_bytes());
+}
+
 #[no_mangle]
 fn bn254_verify_test() {
     let args: Vec<String> = env::args().collect();
@@ -159,6 +385,74 @@ fn bn254_sign_test() {
     db_set("bn254_sign_result", &result_hex).start();
 }
 
+#[no_mangle]
+fn guest_config_test() {
+    assert_eq!(CONFIG.contract_account_id, env::args().nth(1).unwrap());
+
+    execution_result(env::var("WASM_NODE_CONFIG").unwrap().into_bytes());
+}
+
+#[no_mangle]
+fn node_sign_test() {
+    let message = decode_hex(env::args().nth(1).unwrap().as_str()).unwrap();
+    let signature = node_sign(&message);
+    execution_result(encode_hex(&signature.to_compressed().unwrap()).into_bytes());
+}
+
+#[no_mangle]
+fn bn254_aggregate_test() {
+    let args: Vec<String> = env::args().collect();
+
+    // Message
+    let message = decode_hex(args.get(1).unwrap()).unwrap();
+
+    // The private keys of the committee
+    let private_keys: Vec<Bn254PrivateKey> = args[2..]
+        .iter()
+        .map(|private_key_hex| Bn254PrivateKey::try_from(decode_hex(private_key_hex).unwrap().as_ref()).unwrap())
+        .collect();
+    let public_keys: Vec<Bn254PublicKey> = private_keys.iter().map(Bn254PublicKey::from_private_key).collect();
+    let signatures: Vec<Bn254Signature> = private_keys
+        .iter()
+        .map(|private_key| bn254_sign(&message, private_key))
+        .collect();
+
+    let signature = bn254_aggregate_signatures(&signatures);
+    let public_key = bn254_aggregate_public_keys(&public_keys);
+    let results = [
+        encode_hex(&signature.to_compressed().unwrap()),
+        encode_hex(&public_key.to_compressed().unwrap()),
+        bn254_verify_aggregate(&message, &signature, &public_keys).to_string(),
+        bn254_verify_aggregate(&message, &signature, &public_keys[1..]).to_string(),
+    ];
+
+    execution_result(results.join(",").into_bytes());
+}
+
+#[no_mangle]
+fn crypto_test() {
+    let args: Vec<String> = env::args().collect();
+    let message = args.get(1).unwrap().as_bytes();
+    let secp256k1_signature: [u8; 64] = decode_hex(args.get(2).unwrap()).unwrap().try_into().unwrap();
+    let recovery_id: u8 = args.get(3).unwrap().parse().unwrap();
+    let secp256k1_public_key = decode_hex(args.get(4).unwrap()).unwrap();
+    let ed25519_signature: [u8; 64] = decode_hex(args.get(5).unwrap()).unwrap().try_into().unwrap();
+    let ed25519_public_key: [u8; 32] = decode_hex(args.get(6).unwrap()).unwrap().try_into().unwrap();
+
+    let message_hash = sha256(message);
+    let recovered = secp256k1_recover(&message_hash, &secp256k1_signature, recovery_id, true).unwrap();
+    let results = [
+        encode_hex(&message_hash),
+        encode_hex(&keccak256(message)),
+        secp256k1_verify(&message_hash, &secp256k1_signature, &secp256k1_public_key).to_string(),
+        encode_hex(&recovered),
+        ed25519_verify(message, &ed25519_signature, &ed25519_public_key).to_string(),
+        ed25519_verify(b"tampered", &ed25519_signature, &ed25519_public_key).to_string(),
+    ];
+
+    execution_result(results.join(",").into_bytes());
+}
+
 // TODO: Something to include in our SDK? Or bn254 lib. Or use hex crate.
 fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
     (0..s.len())
@@ -177,6 +471,40 @@ fn encode_hex(bytes: &[u8]) -> String {
     result
 }
 
+#[no_mangle]
+fn infinite_loop_test() {
+    let mut counter: u64 = 0;
+    loop {
+        counter = std::hint::black_box(counter.wrapping_add(1));
+    }
+}
+
+#[no_mangle]
+fn interruptible_loop_test() {
+    memory_write("running", vec![1]);
+    // Calls the host on every iteration, where the host can interrupt it
+    loop {
+        std::hint::black_box(consensus_time_ms());
+    }
+}
+
+#[no_mangle]
+fn memory_limit_test() {
+    let mut allocations: Vec<Vec<u8>> = Vec::new();
+    loop {
+        allocations.push(std::hint::black_box(vec![1; 1024 * 1024]));
+    }
+}
+
+#[no_mangle]
+fn http_fetch_rejected_test() {
+    let args: Vec<String> = env::args().collect();
+
+    http_fetch(args.get(1).unwrap())
+        .start()
+        .then(call_self("test_rejected", vec![]));
+}
+
 #[no_mangle]
 fn test_error_turns_into_rejection() {
     http_fetch("fail!").start().then(call_self("test_rejected", vec![]));